@@ -0,0 +1,54 @@
+//! Filtering throughput of a whole dictionary under a typical set of constraints, after a
+//! first guess, and ranking of all words when nothing is known. Run with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+use words::{matches, Dictionary, Excluded, Included, Position, Word, WordsResult};
+
+fn filter(c: &mut Criterion) {
+    let dictionary = Dictionary::default();
+    let pattern = Word::new("****e").unwrap();
+    let excluded = Excluded::new(vec!['c', 'n', 'h', 'o', 'i', 'l']);
+    let included = Included::new(vec!['s']).and_at_not('r', Position::new(1, 5).unwrap());
+
+    let mut group = c.benchmark_group("filter");
+    group.bench_function("naive", |b| {
+        b.iter(|| {
+            dictionary
+                .words()
+                .iter()
+                .filter(|word| matches(&pattern, word, &excluded, &included))
+                .count()
+        })
+    });
+    group.bench_function("masked", |b| {
+        b.iter(|| dictionary.filter(&pattern, &excluded, &included).len())
+    });
+    let weighted = dictionary.weighted_words();
+    group.bench_function("filter_weighted", |b| {
+        b.iter(|| {
+            let mut result = WordsResult::new(pattern.clone());
+            let candidates = black_box(&weighted).iter().map(|(word, n)| (word, *n));
+            result.filter_weighted(candidates, &excluded, &included)
+        })
+    });
+    group.finish();
+}
+
+fn rank(c: &mut Criterion) {
+    let dictionary = Dictionary::default();
+    let mut result = WordsResult::new(Word::new("*****").unwrap());
+    result.filter_slice(
+        dictionary.words(),
+        &Excluded::default(),
+        &Included::default(),
+    );
+
+    c.bench_function("rank_all_words", |b| {
+        b.iter(|| black_box(&result).ranked().len())
+    });
+}
+
+criterion_group!(benches, filter, rank);
+criterion_main!(benches);
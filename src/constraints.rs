@@ -0,0 +1,542 @@
+use std::collections::BTreeSet;
+
+use thiserror::Error;
+
+use crate::{
+    Character, Excluded, Feedback, Included, Position, Tile, Word, WordError, DEFAULT_WORD_LENGTH,
+};
+
+/// Contradiction between constraints, so that no word can satisfy all of them.
+#[derive(Debug, Error, PartialEq)]
+pub enum Conflict {
+    #[error("Letter '{letter}' is known to be at position {index}, but it is also excluded")]
+    FixedAndExcluded { letter: char, index: usize },
+    #[error("Letter '{0}' is known to be in the word, but it is also excluded")]
+    PresentAndExcluded(char),
+    #[error("Letter '{letter}' is known to be at position {index} and known not to be there")]
+    MisplacedAndFixed { letter: char, index: usize },
+    #[error("Letter '{letter}' is known to appear {required} times, but at most {max} times")]
+    OverLimit {
+        letter: char,
+        max: usize,
+        required: usize,
+    },
+    #[error("Word of length {length} can not contain all {required} known letters")]
+    TooManyLetters { required: usize, length: usize },
+}
+
+/// Numbers of letters known so far, see [`Constraints::summary`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ConstraintStats {
+    /// Positions with a known letter.
+    pub greens: usize,
+    /// Letters known to be in the word, but not yet at a known position.
+    pub yellows: usize,
+    /// Letters known not to be in the word.
+    pub greys: usize,
+}
+
+/// Accumulates what is known about the answer over several guesses.
+#[derive(Debug)]
+pub struct Constraints {
+    greens: Vec<Option<char>>,
+    yellows: Vec<(char, Position)>,
+    greys: Vec<char>,
+    max_counts: Vec<(char, usize)>,
+    /// Letters that got a green or yellow tile more than once in a single guess.
+    min_counts: Vec<(char, usize)>,
+    /// Grey repeats of letters that are in the word, but not at these positions.
+    not_at: Vec<(char, Position)>,
+    hard_mode: bool,
+}
+
+impl Constraints {
+    /// Creates constraints for words of given length, with nothing known yet.
+    pub fn new(length: usize) -> Self {
+        Self {
+            greens: vec![None; length],
+            yellows: Vec::new(),
+            greys: Vec::new(),
+            max_counts: Vec::new(),
+            min_counts: Vec::new(),
+            not_at: Vec::new(),
+            hard_mode: false,
+        }
+    }
+
+    /// In hard mode every guess has to reuse all revealed hints: green letters at their
+    /// positions and yellow letters anywhere in the word.
+    pub fn set_hard_mode(&mut self, hard_mode: bool) -> &mut Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Checks whether `guess` can be played next. Outside of hard mode any word can.
+    pub fn allows_guess(&self, guess: &Word) -> bool {
+        if !self.hard_mode {
+            return true;
+        }
+
+        let greens_kept = self
+            .greens
+            .iter()
+            .enumerate()
+            .all(|(i, green)| match green {
+                Some(c) => guess.0.get(i) == Some(&Character::Normal(*c)),
+                None => true,
+            });
+
+        greens_kept
+            && self
+                .yellows
+                .iter()
+                .all(|(c, _)| guess.0.contains(&Character::Normal(*c)))
+    }
+
+    /// Adds a guess and its colored result, e.g. `"crane"` and `"GYBBG"`.
+    pub fn add_guess(&mut self, guess: &str, feedback: &str) -> Result<&mut Self, WordError> {
+        self.add_feedback(&Feedback::new(guess, feedback)?)
+    }
+
+    /// Adds an already parsed guess and its colored result.
+    pub fn add_feedback(&mut self, feedback: &Feedback) -> Result<&mut Self, WordError> {
+        if feedback.guess.len() != self.greens.len() {
+            return Err(WordError::InvalidWordLength {
+                expected: self.greens.len(),
+                actual: feedback.guess.len(),
+            });
+        }
+
+        let letters: Vec<(usize, char, Tile)> = feedback
+            .guess
+            .0
+            .iter()
+            .zip(feedback.tiles.iter())
+            .enumerate()
+            .filter_map(|(i, (c, tile))| match c {
+                Character::Normal(c) => Some((i, *c, *tile)),
+                _ => None,
+            })
+            .collect();
+
+        for (i, letter, tile) in letters.iter() {
+            let found = letters
+                .iter()
+                .filter(|(_, l, t)| l == letter && *t != Tile::Absent)
+                .count();
+            if found > 1 {
+                match self.min_counts.iter_mut().find(|(l, _)| l == letter) {
+                    Some((_, min)) => *min = (*min).max(found),
+                    None => self.min_counts.push((*letter, found)),
+                }
+            }
+
+            match tile {
+                Tile::Correct => self.greens[*i] = Some(*letter),
+                Tile::Present => {
+                    let position = Position::new(*i, self.greens.len())?;
+                    push_unique(&mut self.yellows, (*letter, position));
+                }
+                Tile::Absent => {
+                    // A grey tile only means "no more of this letter" when the same
+                    // letter got a green or yellow tile elsewhere in this guess, but
+                    // the letter is still not at this position.
+                    if found == 0 {
+                        push_unique(&mut self.greys, *letter);
+                    } else {
+                        push_unique(&mut self.max_counts, (*letter, found));
+                        let position = Position::new(*i, self.greens.len())?;
+                        push_unique(&mut self.not_at, (*letter, position));
+                    }
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Checks that the accumulated hints don't contradict each other, e.g. when a letter
+    /// was entered as grey in one guess but green in another.
+    pub fn validate(&self) -> Result<(), Conflict> {
+        for (index, green) in self.greens.iter().enumerate() {
+            if let Some(letter) = green {
+                if self.greys.contains(letter) {
+                    return Err(Conflict::FixedAndExcluded {
+                        letter: *letter,
+                        index,
+                    });
+                }
+            }
+        }
+
+        for (letter, position) in self.not_at.iter() {
+            if self.greens.get(position.index()) == Some(&Some(*letter)) {
+                return Err(Conflict::FixedAndExcluded {
+                    letter: *letter,
+                    index: position.index(),
+                });
+            }
+        }
+
+        for (letter, index) in self.yellows.iter() {
+            if self.greys.contains(letter) {
+                return Err(Conflict::PresentAndExcluded(*letter));
+            }
+            if self.greens.get(index.index()) == Some(&Some(*letter)) {
+                return Err(Conflict::MisplacedAndFixed {
+                    letter: *letter,
+                    index: index.index(),
+                });
+            }
+        }
+
+        let mut required: Vec<(char, usize)> = Vec::new();
+        for letter in self.greens.iter().flatten() {
+            match required.iter_mut().find(|(l, _)| l == letter) {
+                Some((_, count)) => *count += 1,
+                None => required.push((*letter, 1)),
+            }
+        }
+        for (letter, _) in self.yellows.iter() {
+            if !required.iter().any(|(l, _)| l == letter) {
+                required.push((*letter, 1));
+            }
+        }
+        for (letter, min) in self.min_counts.iter() {
+            match required.iter_mut().find(|(l, _)| l == letter) {
+                Some((_, count)) => *count = (*count).max(*min),
+                None => required.push((*letter, *min)),
+            }
+        }
+
+        for (letter, max) in self.max_counts.iter() {
+            if let Some((_, count)) = required.iter().find(|(l, _)| l == letter) {
+                if count > max {
+                    return Err(Conflict::OverLimit {
+                        letter: *letter,
+                        max: *max,
+                        required: *count,
+                    });
+                }
+            }
+        }
+
+        let total: usize = required.iter().map(|(_, count)| count).sum();
+        if total > self.greens.len() {
+            return Err(Conflict::TooManyLetters {
+                required: total,
+                length: self.greens.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Counts green, yellow and grey letters, e.g. for a status line. A yellow letter that
+    /// later turned green is counted only as green.
+    pub fn summary(&self) -> ConstraintStats {
+        let mut yellows: Vec<char> = Vec::new();
+        for (letter, _) in self.yellows.iter() {
+            if !self.greens.contains(&Some(*letter)) {
+                push_unique(&mut yellows, *letter);
+            }
+        }
+
+        ConstraintStats {
+            greens: self.greens.iter().flatten().count(),
+            yellows: yellows.len(),
+            greys: self.greys.len(),
+        }
+    }
+
+    /// Letters, of `A` to `Z`, that can not be at `position` of the answer, without looking
+    /// at any dictionary: all but the green letter there, grey letters, yellow letters
+    /// guessed at this position and letters already green elsewhere as many times as they
+    /// can appear.
+    pub fn impossible_at(&self, position: Position) -> BTreeSet<char> {
+        let index = position.index();
+        if let Some(Some(green)) = self.greens.get(index) {
+            return ('A'..='Z').filter(|c| c != green).collect();
+        }
+
+        let mut impossible: BTreeSet<char> = self.greys.iter().copied().collect();
+        impossible.extend(
+            self.yellows
+                .iter()
+                .chain(self.not_at.iter())
+                .filter(|(_, p)| *p == position)
+                .map(|(letter, _)| *letter),
+        );
+        impossible.extend(
+            self.max_counts
+                .iter()
+                .filter(|(letter, max)| {
+                    self.greens
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, green)| *i != index && **green == Some(*letter))
+                        .count()
+                        >= *max
+                })
+                .map(|(letter, _)| *letter),
+        );
+        impossible
+    }
+
+    /// Pattern with green letters at their positions and wildcards everywhere else.
+    pub fn pattern(&self) -> Word {
+        Word(
+            self.greens
+                .iter()
+                .map(|c| match c {
+                    Some(c) => Character::Normal(*c),
+                    None => Character::Wildcard,
+                })
+                .collect(),
+        )
+    }
+
+    /// Letters known to be absent, limited to a number of occurrences, or absent only at
+    /// positions where they were grey.
+    pub fn excluded(&self) -> Excluded {
+        let excluded = self.max_counts.iter().fold(
+            Excluded::new(self.greys.clone()),
+            |excluded, (letter, count)| excluded.and_at_most(*letter, *count),
+        );
+        self.not_at
+            .iter()
+            .fold(excluded, |excluded, (letter, position)| {
+                excluded.and_at(*letter, *position)
+            })
+    }
+
+    /// Letters known to be in the word, but not at the positions they were guessed at, and
+    /// letters known to appear at least as many times as they were green or yellow in a
+    /// single guess.
+    pub fn included(&self) -> Included {
+        let included = self
+            .yellows
+            .iter()
+            .fold(Included::default(), |included, (letter, position)| {
+                included.and_at_not(*letter, *position)
+            });
+        self.min_counts
+            .iter()
+            .fold(included, |included, (letter, min)| {
+                let max = self
+                    .max_counts
+                    .iter()
+                    .find(|(l, _)| l == letter)
+                    .map_or(self.greens.len(), |(_, max)| *max);
+                included.and_count(*letter, *min, max)
+            })
+    }
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Self::new(DEFAULT_WORD_LENGTH)
+    }
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::WordsResult;
+
+    #[test]
+    fn should_accumulate_colors_from_all_guesses() {
+        let mut constraints = Constraints::default();
+        constraints
+            .add_guess("crane", "BYBBG")
+            .unwrap()
+            .add_guess("horse", "BBGGG")
+            .unwrap();
+
+        assert_eq!(constraints.pattern(), Word::new("**rse").unwrap());
+        assert_eq!(
+            constraints.excluded().letters,
+            vec!['C', 'A', 'N', 'H', 'O']
+        );
+        assert_eq!(
+            constraints.included().misplaced,
+            vec![('R', Position::new(1, 5).unwrap())]
+        );
+    }
+
+    #[test]
+    fn should_limit_letter_count_when_repeated_letter_is_grey() {
+        let mut constraints = Constraints::default();
+        constraints.add_guess("llama", "GBGGB").unwrap();
+
+        let excluded = constraints.excluded();
+        assert!(excluded.letters.is_empty());
+        assert_eq!(excluded.max_counts, vec![('L', 1), ('A', 1)]);
+    }
+
+    #[test]
+    fn should_exclude_grey_repeated_letter_at_its_position() {
+        let mut constraints = Constraints::default();
+        constraints.add_guess("speed", "BBYBY").unwrap();
+
+        let excluded = constraints.excluded();
+        assert_eq!(excluded.max_counts, vec![('E', 1)]);
+        assert_eq!(
+            excluded.positions,
+            vec![('E', Position::new(3, 5).unwrap())]
+        );
+
+        let mut result = WordsResult::new(constraints.pattern());
+        let included = constraints.included();
+        assert!(result.is_word_possible("abide", &excluded, &included));
+        assert!(!result.is_word_possible("dimer", &excluded, &included));
+    }
+
+    #[test]
+    fn should_require_letter_as_many_times_as_it_was_green_or_yellow() {
+        let mut constraints = Constraints::default();
+        constraints.add_guess("eerie", "GYBBB").unwrap();
+
+        let included = constraints.included();
+        assert_eq!(included.counts, vec![('E', 2, 2)]);
+
+        let excluded = constraints.excluded();
+        let mut result = WordsResult::new(constraints.pattern());
+        assert!(result.is_word_possible("eaten", &excluded, &included));
+        assert!(!result.is_word_possible("ethos", &excluded, &included));
+    }
+
+    #[test]
+    fn should_return_error_if_guess_has_different_length() {
+        let mut constraints = Constraints::default();
+        let actual = constraints.add_guess("plants", "BBBBBB").unwrap_err();
+        let expected = WordError::InvalidWordLength {
+            expected: 5,
+            actual: 6,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_allow_only_guesses_reusing_hints_in_hard_mode() {
+        let mut constraints = Constraints::default();
+        constraints.add_guess("crane", "GYBBB").unwrap();
+        let slate = Word::new("slate").unwrap();
+        let curio = Word::new("curio").unwrap();
+
+        assert!(constraints.allows_guess(&slate));
+        constraints.set_hard_mode(true);
+        assert!(!constraints.allows_guess(&slate));
+        assert!(!constraints.allows_guess(&Word::new("cloth").unwrap()));
+        assert!(constraints.allows_guess(&curio));
+    }
+
+    #[test]
+    fn should_find_no_conflict_in_consistent_hints() {
+        let mut constraints = Constraints::default();
+        constraints
+            .add_guess("crane", "BYBBG")
+            .unwrap()
+            .add_guess("horse", "BBGGG")
+            .unwrap();
+
+        assert_eq!(constraints.validate(), Ok(()));
+    }
+
+    #[test]
+    fn should_find_conflicts_between_guesses() {
+        let mut constraints = Constraints::default();
+        constraints
+            .add_guess("crane", "BBBBB")
+            .unwrap()
+            .add_guess("horse", "BBBBG")
+            .unwrap();
+        assert_eq!(
+            constraints.validate(),
+            Err(Conflict::FixedAndExcluded {
+                letter: 'E',
+                index: 4
+            })
+        );
+
+        let mut constraints = Constraints::default();
+        constraints
+            .add_guess("abcde", "YYYYY")
+            .unwrap()
+            .add_guess("fghij", "YBBBB")
+            .unwrap();
+        assert_eq!(
+            constraints.validate(),
+            Err(Conflict::TooManyLetters {
+                required: 6,
+                length: 5
+            })
+        );
+    }
+
+    #[test]
+    fn should_filter_words_with_accumulated_constraints() {
+        let mut constraints = Constraints::default();
+        constraints.add_guess("crane", "BYBBG").unwrap();
+
+        let excluded = constraints.excluded();
+        let included = constraints.included();
+        let mut result = WordsResult::new(constraints.pattern());
+
+        assert!(result.is_word_possible("horse", &excluded, &included));
+        assert!(!result.is_word_possible("rinse", &excluded, &included));
+        assert!(!result.is_word_possible("bribe", &excluded, &included));
+        assert_eq!(result.possible_words, vec![Word::new("horse").unwrap()]);
+    }
+
+    #[test]
+    fn should_count_letters_of_each_color() {
+        let mut constraints = Constraints::default();
+        assert_eq!(constraints.summary(), ConstraintStats::default());
+
+        constraints
+            .add_guess("crane", "BYYBG")
+            .unwrap()
+            .add_guess("rathe", "YGBBG")
+            .unwrap();
+
+        let expected = ConstraintStats {
+            greens: 2,
+            yellows: 1,
+            greys: 4,
+        };
+        assert_eq!(constraints.summary(), expected);
+    }
+
+    #[test]
+    fn should_find_letters_impossible_at_position() {
+        let mut constraints = Constraints::default();
+        constraints.add_guess("eerie", "YBYBG").unwrap();
+
+        let position = |i| Position::new(i, 5).unwrap();
+        assert_eq!(
+            constraints.impossible_at(position(0)),
+            BTreeSet::from(['E', 'I'])
+        );
+        assert_eq!(
+            constraints.impossible_at(position(2)),
+            BTreeSet::from(['I', 'R'])
+        );
+        assert_eq!(constraints.impossible_at(position(4)).len(), 25);
+        assert!(!constraints.impossible_at(position(4)).contains(&'E'));
+
+        let mut constraints = Constraints::default();
+        constraints.add_guess("llama", "GBGGB").unwrap();
+        assert_eq!(
+            constraints.impossible_at(position(1)),
+            BTreeSet::from(['A', 'L'])
+        );
+    }
+}
@@ -0,0 +1,635 @@
+use std::collections::HashMap;
+
+use crate::{
+    default_words, feedback, mask_allows, matches, rank, split_weight, to_uppercase, Character,
+    Constraints, Excluded, Included, Word, WordError, DEFAULT_WORD_LENGTH,
+};
+
+/// List of words parsed once, so it can be filtered many times.
+#[derive(Debug)]
+pub struct Dictionary {
+    words: Vec<Word>,
+    weights: Vec<u32>,
+    masks: Vec<u32>,
+    roles: Vec<Role>,
+    /// Index of the first occurrence of every word, to look words up quickly.
+    indices: HashMap<Word, usize>,
+    skipped: usize,
+}
+
+/// Which list a word of the dictionary comes from, see [`Dictionary::with_roles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Word that can be the answer, and can also be guessed.
+    Answer,
+    /// Word that is allowed as a guess, but is never the answer.
+    Guess,
+}
+
+impl Dictionary {
+    /// Parses every line as a word of given length, optionally followed by its weight,
+    /// see [`split_weight`]. Lines of different length are left out, lines with invalid
+    /// chars are left out and counted as skipped.
+    pub fn new<S: AsRef<str>>(lines: impl IntoIterator<Item = S>, length: usize) -> Self {
+        let mut words = Vec::new();
+        let mut weights = Vec::new();
+        let mut skipped = 0;
+
+        for line in lines {
+            let (line, weight) = split_weight(line.as_ref());
+            match Word::with_length(line, length) {
+                Ok(word) => {
+                    words.push(word);
+                    weights.push(weight);
+                }
+                Err(WordError::InvalidWordLength { .. }) => {}
+                Err(_) => skipped += 1,
+            }
+        }
+
+        let masks = words.iter().map(Word::letter_mask).collect();
+        let roles = vec![Role::Answer; words.len()];
+        let mut indices = HashMap::with_capacity(words.len());
+        for (i, word) in words.iter().enumerate() {
+            indices.entry(word.clone()).or_insert(i);
+        }
+        Self {
+            words,
+            weights,
+            masks,
+            roles,
+            indices,
+            skipped,
+        }
+    }
+
+    /// Parses two lists like [`Dictionary::new`], words that can be the answer and words
+    /// that are only allowed as guesses, the way Wordle keeps them. Guesses are picked
+    /// from all words, but only answers are matched, solved and ranked against. Words
+    /// that are on both lists are answers.
+    pub fn with_roles<S: AsRef<str>, T: AsRef<str>>(
+        answers: impl IntoIterator<Item = S>,
+        guesses: impl IntoIterator<Item = T>,
+        length: usize,
+    ) -> Self {
+        let mut dictionary = Self::new(answers, length);
+        let guesses = Self::new(guesses, length);
+
+        for ((word, weight), mask) in guesses
+            .words
+            .into_iter()
+            .zip(guesses.weights)
+            .zip(guesses.masks)
+        {
+            if !dictionary.indices.contains_key(&word) {
+                dictionary
+                    .indices
+                    .insert(word.clone(), dictionary.words.len());
+                dictionary.words.push(word);
+                dictionary.weights.push(weight);
+                dictionary.masks.push(mask);
+                dictionary.roles.push(Role::Guess);
+            }
+        }
+        dictionary.skipped += guesses.skipped;
+        dictionary
+    }
+
+    /// Checks every line the way [`Dictionary::new`] parses it, but reports all lines that
+    /// are not valid words of given length, together with their 1-based line numbers.
+    pub fn validate<S: AsRef<str>>(
+        lines: impl IntoIterator<Item = S>,
+        length: usize,
+    ) -> Vec<(usize, WordError)> {
+        lines
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                Word::with_length(split_weight(line.as_ref()).0, length)
+                    .err()
+                    .map(|e| (i + 1, e))
+            })
+            .collect()
+    }
+
+    /// All words, answers and guesses, see [`Dictionary::with_roles`].
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    /// Words that can be the answer, all of them unless loaded with
+    /// [`Dictionary::with_roles`].
+    pub fn answers(&self) -> Vec<&Word> {
+        self.words
+            .iter()
+            .zip(self.roles.iter())
+            .filter(|(_, role)| **role == Role::Answer)
+            .map(|(word, _)| word)
+            .collect()
+    }
+
+    /// List `word` comes from, `None` when it is not in the dictionary.
+    pub fn role(&self, word: &Word) -> Option<Role> {
+        self.indices.get(word).map(|i| self.roles[*i])
+    }
+
+    /// Words together with the weights they were loaded with.
+    pub fn weighted_words(&self) -> Vec<(Word, u32)> {
+        self.words
+            .iter()
+            .cloned()
+            .zip(self.weights.iter().copied())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Number of lines that were skipped because they contain invalid chars.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Word that is expected to split `candidates` into the most, and most even, groups by
+    /// the feedback it would get, i.e. the guess giving the most information. When several
+    /// words are equally good, one of the candidates is preferred.
+    pub fn best_guess(&self, candidates: &[&Word]) -> Option<&Word> {
+        let mut best: Option<(&Word, f64)> = None;
+
+        for word in self.words.iter() {
+            let score = rank::entropy(word, candidates);
+            best = match best {
+                Some((_, best_score)) if score > best_score => Some((word, score)),
+                Some((best_word, best_score))
+                    if score == best_score
+                        && !candidates.contains(&best_word)
+                        && candidates.contains(&word) =>
+                {
+                    Some((word, score))
+                }
+                None => Some((word, score)),
+                _ => best,
+            };
+        }
+
+        best.map(|(word, _)| word)
+    }
+
+    /// Plays a whole game against a known `answer` and returns all guesses made, the last
+    /// one being the answer if it was found. The game starts with `first_guess`, or with
+    /// [`Dictionary::recommended_opener`] if none is given, then every next guess is
+    /// [`Dictionary::best_guess`] for the words still matching all feedback so far.
+    pub fn solve(&self, answer: &Word, first_guess: Option<&Word>) -> Vec<Word> {
+        let mut guesses = Vec::new();
+        let mut candidates = self.answers();
+        let mut guess = first_guess.or_else(|| self.recommended_opener());
+
+        while let Some(current) = guess {
+            guesses.push(current.clone());
+            if current == answer {
+                break;
+            }
+
+            let tiles = feedback(current, answer);
+            candidates.retain(|candidate| feedback(current, candidate) == tiles);
+            if candidates.is_empty() {
+                break;
+            }
+
+            guess = self.best_guess(&candidates);
+        }
+
+        guesses
+    }
+
+    /// Average number of guesses [`Dictionary::solve`] needs when every answer of the
+    /// dictionary is the answer once, e.g. to compare openers given as `first_guess`.
+    pub fn average_guesses(&self, first_guess: Option<&Word>) -> f64 {
+        self.average_guesses_with_progress(first_guess, |_, _| {})
+    }
+
+    /// Same as [`Dictionary::average_guesses`], calling `progress` with the number of
+    /// answers solved so far and the number of all answers after every game.
+    pub fn average_guesses_with_progress(
+        &self,
+        first_guess: Option<&Word>,
+        mut progress: impl FnMut(usize, usize),
+    ) -> f64 {
+        let answers = self.answers();
+        if answers.is_empty() {
+            return 0.0;
+        }
+
+        let mut total = 0;
+        for (i, answer) in answers.iter().enumerate() {
+            total += self.solve(answer, first_guess).len();
+            progress(i + 1, answers.len());
+        }
+        total as f64 / answers.len() as f64
+    }
+
+    /// Returns words that can be played as the next guess, see [`Constraints::allows_guess`].
+    pub fn guesses(&self, constraints: &Constraints) -> Vec<&Word> {
+        self.words
+            .iter()
+            .filter(|word| constraints.allows_guess(word))
+            .collect()
+    }
+
+    /// Returns words made of exactly the given letters, in any order.
+    pub fn anagrams(&self, letters: &str) -> Vec<&Word> {
+        let mut letters: Vec<char> = letters.chars().map(to_uppercase).collect();
+        letters.sort_unstable();
+
+        self.words
+            .iter()
+            .filter(|word| {
+                let mut word_letters: Vec<char> = word
+                    .0
+                    .iter()
+                    .filter_map(|c| match c {
+                        Character::Normal(c) => Some(*c),
+                        _ => None,
+                    })
+                    .collect();
+                word_letters.sort_unstable();
+                word_letters == letters
+            })
+            .collect()
+    }
+
+    /// For every position, how many answers of the dictionary have given letter there.
+    pub fn positional_frequencies(&self) -> Vec<HashMap<char, u32>> {
+        rank::positional_frequencies(self.answers().into_iter())
+    }
+
+    /// Every word with the information, in bits, it gives as the first guess when any
+    /// answer of the dictionary can be the answer, best first. Checks every word against
+    /// every answer, so it is slow for big dictionaries. See [`crate::write_openers`] to
+    /// save it.
+    pub fn rank_openers(&self) -> Vec<(&Word, f64)> {
+        self.rank_openers_with_progress(|_, _| {})
+    }
+
+    /// Same as [`Dictionary::rank_openers`], calling `progress` with the number of words
+    /// scored so far and the number of all words after every word, e.g. to show that
+    /// ranking a big dictionary is not stuck.
+    pub fn rank_openers_with_progress(
+        &self,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Vec<(&Word, f64)> {
+        let answers = self.answers();
+        let mut ranked: Vec<(&Word, f64)> = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                let score = rank::entropy(word, &answers);
+                progress(i + 1, self.len());
+                (word, score)
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        ranked
+    }
+
+    /// Word whose distinct letters are most common at their positions across
+    /// all answers of the dictionary, times its weight, a good first guess.
+    pub fn recommended_opener(&self) -> Option<&Word> {
+        let frequencies = self.positional_frequencies();
+        let answers = self.answers().len();
+        self.words
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(word, weight)| {
+                let score = rank::frequency_score(word, &frequencies, answers);
+                (word, score * *weight as f64)
+            })
+            .fold(
+                None,
+                |best: Option<(&Word, f64)>, (word, score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((word, score)),
+                },
+            )
+            .map(|(word, _)| word)
+    }
+
+    /// Returns answers that match `pattern` and the excluded/included constraints. Letter
+    /// masks of all words are computed when loading, so most words with an excluded
+    /// letter or without an included one are rejected before checking them letter by letter.
+    pub fn filter(&self, pattern: &Word, excluded: &Excluded, included: &Included) -> Vec<&Word> {
+        let (excluded_mask, included_mask) = (excluded.letter_mask(), included.letter_mask());
+        self.words
+            .iter()
+            .zip(self.masks.iter())
+            .zip(self.roles.iter())
+            .filter(|(_, role)| **role == Role::Answer)
+            .map(|(word_mask, _)| word_mask)
+            .filter(|(_, mask)| mask_allows(**mask, excluded_mask, included_mask))
+            .map(|(word, _)| word)
+            .filter(|word| matches(pattern, word, excluded, included))
+            .collect()
+    }
+
+    /// Same as [`Dictionary::filter`], but checks words in parallel. Words are still
+    /// returned in dictionary order, so both methods give equal results.
+    #[cfg(feature = "rayon")]
+    pub fn par_filter(
+        &self,
+        pattern: &Word,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> Vec<&Word> {
+        use rayon::prelude::*;
+
+        let (excluded_mask, included_mask) = (excluded.letter_mask(), included.letter_mask());
+        self.words
+            .par_iter()
+            .zip(self.masks.par_iter())
+            .zip(self.roles.par_iter())
+            .filter(|(_, role)| **role == Role::Answer)
+            .map(|(word_mask, _)| word_mask)
+            .filter(|(_, mask)| mask_allows(**mask, excluded_mask, included_mask))
+            .map(|(word, _)| word)
+            .filter(|word| matches(pattern, word, excluded, included))
+            .collect()
+    }
+}
+
+/// Dictionary built from the word list embedded in the binary.
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::new(default_words(), DEFAULT_WORD_LENGTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    #[cfg(feature = "rayon")]
+    use crate::Position;
+
+    #[test]
+    fn should_parse_valid_lines_and_count_skipped_ones() {
+        let dictionary = Dictionary::new(["zorro", "zowie", "", "ab3de", "plants"], 5);
+
+        assert_eq!(
+            dictionary.words(),
+            &[Word::new("zorro").unwrap(), Word::new("zowie").unwrap()]
+        );
+        assert_eq!(dictionary.skipped(), 1);
+    }
+
+    #[test]
+    fn should_skip_words_with_apostrophes_and_hyphens() {
+        let dictionary = Dictionary::new(["don't", "t-bar", "dough"], 5);
+        assert_eq!(dictionary.skipped(), 2);
+
+        let pattern = Word::new("d****").unwrap();
+        let actual = dictionary.filter(&pattern, &Excluded::default(), &Included::default());
+        assert_eq!(actual, vec![&Word::new("dough").unwrap()]);
+    }
+
+    #[test]
+    fn should_report_invalid_lines_with_their_numbers() {
+        let actual = Dictionary::validate(["zorro", "plants", "zowie", "ab3de"], 5);
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    2,
+                    WordError::InvalidWordLength {
+                        expected: 5,
+                        actual: 6
+                    }
+                ),
+                (4, WordError::InvalidCharValue('3')),
+            ]
+        );
+        assert!(Dictionary::validate(default_words(), 5).is_empty());
+    }
+
+    #[test]
+    fn should_load_weights_defaulting_to_one() {
+        let dictionary = Dictionary::new(["sales 3", "tales", "males 40"], 5);
+        let weights: Vec<u32> = dictionary
+            .weighted_words()
+            .into_iter()
+            .map(|(_, weight)| weight)
+            .collect();
+
+        assert_eq!(weights, vec![3, 1, 40]);
+        assert_eq!(
+            dictionary.recommended_opener(),
+            Some(&Word::new("males").unwrap())
+        );
+    }
+
+    #[test]
+    fn should_filter_words_many_times() {
+        let dictionary = Dictionary::new(["zorro", "zowie", "morro", "aargh"], 5);
+        let included = Included::default();
+
+        let actual = dictionary.filter(
+            &Word::new("*orro").unwrap(),
+            &Excluded::default(),
+            &included,
+        );
+        assert_eq!(actual, vec![&dictionary.words()[0], &dictionary.words()[2]]);
+
+        let excluded = Excluded::new(vec!['m']);
+        let actual = dictionary.filter(&Word::new("*orro").unwrap(), &excluded, &included);
+        assert_eq!(actual, vec![&dictionary.words()[0]]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn should_filter_the_same_words_in_parallel() {
+        let dictionary = Dictionary::default();
+        let pattern = Word::new("*a***").unwrap();
+        let excluded = Excluded::new(vec!['e', 's']);
+        let included = Included::at_not('r', Position::new(0, 5).unwrap());
+
+        let expected = dictionary.filter(&pattern, &excluded, &included);
+        let actual = dictionary.par_filter(&pattern, &excluded, &included);
+        assert!(!expected.is_empty());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_count_letters_at_each_position_of_dictionary() {
+        let dictionary = Dictionary::new(["sassy", "sales", "tales", "males"], 5);
+        let actual = dictionary.positional_frequencies();
+
+        assert_eq!(actual[0][&'S'], 2);
+        assert_eq!(actual[1][&'A'], 4);
+        assert_eq!(actual[4][&'S'], 3);
+    }
+
+    #[test]
+    fn should_recommend_opener_with_most_common_letters() {
+        let dictionary = Dictionary::new(["sassy", "sales", "tales", "males"], 5);
+        assert_eq!(
+            dictionary.recommended_opener(),
+            Some(&Word::new("tales").unwrap())
+        );
+        assert_eq!(Dictionary::new(["zorro"], 6).recommended_opener(), None);
+    }
+
+    #[test]
+    fn should_pick_guess_splitting_candidates_the_most() {
+        let dictionary = Dictionary::new(["zzzzz", "baker", "cater", "later", "water"], 5);
+        let candidates: Vec<&Word> = dictionary.words()[2..].iter().collect();
+        assert_eq!(
+            dictionary.best_guess(&candidates),
+            Some(&Word::new("cater").unwrap())
+        );
+
+        let candidates: Vec<&Word> = dictionary.words()[3..4].iter().collect();
+        assert_eq!(
+            dictionary.best_guess(&candidates),
+            Some(&Word::new("later").unwrap())
+        );
+        assert_eq!(Dictionary::new(["zorro"], 6).best_guess(&[]), None);
+    }
+
+    #[test]
+    fn should_solve_game_ending_with_the_answer() {
+        let dictionary = Dictionary::new(["baker", "cater", "later", "water", "tales"], 5);
+        let answer = Word::new("water").unwrap();
+
+        let guesses = dictionary.solve(&answer, None);
+        assert_eq!(Some(&guesses[0]), dictionary.recommended_opener());
+        assert_eq!(guesses.last(), Some(&answer));
+
+        let first_guess = Word::new("water").unwrap();
+        assert_eq!(dictionary.solve(&answer, Some(&first_guess)), vec![answer]);
+    }
+
+    #[test]
+    fn should_rank_openers_by_information_they_give() {
+        let dictionary = Dictionary::new(["baker", "cater", "later"], 5);
+        let actual = dictionary.rank_openers();
+
+        assert_eq!(actual[0], (&Word::new("cater").unwrap(), 3f64.log2()));
+        assert_eq!(actual[2].0, &Word::new("baker").unwrap());
+        assert!(actual[2].1 < actual[1].1);
+    }
+
+    #[test]
+    fn should_average_guesses_over_all_answers() {
+        let dictionary = Dictionary::new(["baker", "cater", "later"], 5);
+
+        let baker = Word::new("baker").unwrap();
+        let cater = Word::new("cater").unwrap();
+        assert_eq!(dictionary.average_guesses(Some(&baker)), 2.0);
+        assert_eq!(dictionary.average_guesses(Some(&cater)), 5.0 / 3.0);
+        assert_eq!(Dictionary::new([""; 0], 5).average_guesses(None), 0.0);
+    }
+
+    #[test]
+    fn should_stop_solving_when_answer_is_not_in_dictionary() {
+        let dictionary = Dictionary::new(["baker", "cater", "later"], 5);
+        let guesses = dictionary.solve(&Word::new("zorro").unwrap(), None);
+
+        assert_eq!(guesses.len(), 1);
+    }
+
+    #[test]
+    fn should_leave_out_guesses_breaking_hard_mode() {
+        let dictionary = Dictionary::new(["slate", "curio", "cider"], 5);
+        let mut constraints = Constraints::default();
+        constraints.add_guess("crane", "GYBBB").unwrap();
+
+        assert_eq!(dictionary.guesses(&constraints).len(), 3);
+
+        constraints.set_hard_mode(true);
+        assert_eq!(
+            dictionary.guesses(&constraints),
+            vec![&dictionary.words()[1], &dictionary.words()[2]]
+        );
+
+        // An answer matching the known pattern is still left out as a guess without an R.
+        let dictionary = Dictionary::with_roles(["cabin", "cider"], ["rusty"], 5);
+        let pattern = Word::new("c****").unwrap();
+        let answers = dictionary.filter(&pattern, &Excluded::default(), &Included::default());
+        assert!(answers.contains(&&Word::new("cabin").unwrap()));
+        assert_eq!(
+            dictionary.guesses(&constraints),
+            vec![&Word::new("cider").unwrap()]
+        );
+    }
+
+    #[test]
+    fn should_return_anagrams_of_given_letters() {
+        let dictionary = Dictionary::new(["least", "slate", "steal", "tales", "stale", "salts"], 5);
+        let actual = dictionary.anagrams("TaLes");
+
+        assert_eq!(actual.len(), 5);
+        assert!(!actual.contains(&&Word::new("salts").unwrap()));
+        assert!(dictionary.anagrams("tale").is_empty());
+    }
+
+    #[test]
+    fn should_load_embedded_words_by_default() {
+        let dictionary = Dictionary::default();
+        assert_eq!(dictionary.len(), default_words().count());
+        assert_eq!(dictionary.skipped(), 0);
+    }
+
+    #[test]
+    fn should_match_and_rank_only_answers_but_guess_any_word() {
+        let dictionary = Dictionary::with_roles(
+            ["cater", "later", "water"],
+            ["tales", "ttttt", "later", "tales"],
+            5,
+        );
+        let water = Word::new("water").unwrap();
+        let tales = Word::new("tales").unwrap();
+
+        assert_eq!(dictionary.len(), 5);
+        assert_eq!(dictionary.answers().len(), 3);
+        assert_eq!(
+            dictionary.role(&Word::new("later").unwrap()),
+            Some(Role::Answer)
+        );
+        assert_eq!(dictionary.role(&tales), Some(Role::Guess));
+        assert_eq!(dictionary.role(&Word::new("zorro").unwrap()), None);
+
+        let pattern = Word::new("**le*").unwrap();
+        let actual = dictionary.filter(&pattern, &Excluded::default(), &Included::default());
+        assert!(actual.is_empty());
+
+        let guesses = dictionary.solve(&water, None);
+        assert_eq!(guesses.last(), Some(&water));
+        assert_eq!(dictionary.rank_openers().len(), 5);
+    }
+
+    #[test]
+    fn should_report_progress_of_every_word() {
+        let dictionary = Dictionary::new(["baker", "cater", "later"], 5);
+
+        let mut reported = Vec::new();
+        let ranked = dictionary.rank_openers_with_progress(|done, total| {
+            reported.push((done, total));
+        });
+        assert_eq!(ranked, dictionary.rank_openers());
+        assert_eq!(reported, vec![(1, 3), (2, 3), (3, 3)]);
+
+        let mut solved = 0;
+        let baker = Word::new("baker").unwrap();
+        let average = dictionary.average_guesses_with_progress(Some(&baker), |done, _| {
+            solved = done;
+        });
+        assert_eq!(average, 2.0);
+        assert_eq!(solved, 3);
+    }
+}
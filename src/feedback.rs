@@ -0,0 +1,298 @@
+use std::{fmt::Display, io::BufRead, str::FromStr};
+
+use crate::{Character, Error, Word, WordError, WILDCARDS};
+
+/// Color of a single letter in Wordle's answer to a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tile {
+    /// Green - letter is in the word and in the correct spot.
+    Correct,
+    /// Yellow - letter is in the word but in a different spot.
+    Present,
+    /// Grey - letter is not in the word (or not that many times).
+    Absent,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = WordError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_ascii_uppercase() {
+            'G' => Ok(Self::Correct),
+            'Y' => Ok(Self::Present),
+            'B' | 'X' => Ok(Self::Absent),
+            _ => Err(WordError::InvalidTileValue(value)),
+        }
+    }
+}
+
+/// Writes the tile as the letter used to enter it, `G`, `Y` or `B`.
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Self::Correct => 'G',
+            Self::Present => 'Y',
+            Self::Absent => 'B',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// A single guess together with the colors Wordle revealed for it.
+#[derive(Debug, Clone)]
+pub struct Feedback {
+    pub(crate) guess: Word,
+    pub(crate) tiles: Vec<Tile>,
+}
+
+impl Feedback {
+    /// Creates feedback from a guess and its colored result, e.g. `"crane"` and `"GYBBG"`,
+    /// where `G` is green, `Y` is yellow and `B` (or `X`) is grey.
+    pub fn new(guess: &str, result: &str) -> Result<Self, WordError> {
+        let (guess, result) = (guess.trim(), result.trim());
+        if let Some(c) = guess.chars().find(|c| WILDCARDS.contains(c) || *c == '[') {
+            return Err(WordError::InvalidCharValue(c));
+        }
+        let guess = Word::with_length(guess, guess.chars().count())?;
+
+        let length = result.chars().count();
+        if length != guess.len() {
+            return Err(WordError::InvalidWordLength {
+                expected: guess.len(),
+                actual: length,
+            });
+        }
+
+        let tiles = result
+            .chars()
+            .map(Tile::try_from)
+            .collect::<Result<Vec<Tile>, WordError>>()?;
+
+        Ok(Self { guess, tiles })
+    }
+
+    /// Feedback that `guess` got, e.g. computed with [`feedback`].
+    pub fn from_tiles(guess: Word, tiles: Vec<Tile>) -> Result<Self, WordError> {
+        if tiles.len() != guess.len() {
+            return Err(WordError::InvalidWordLength {
+                expected: guess.len(),
+                actual: tiles.len(),
+            });
+        }
+        Ok(Self { guess, tiles })
+    }
+
+    pub fn guess(&self) -> &Word {
+        &self.guess
+    }
+
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Checks whether `candidate` would produce exactly this feedback if it was the answer.
+    pub fn is_satisfied_by(&self, candidate: &Word) -> bool {
+        candidate.len() == self.guess.len() && feedback(&self.guess, candidate) == self.tiles
+    }
+}
+
+impl FromStr for Feedback {
+    type Err = WordError;
+
+    /// Parses feedback given as `guess:result` or `guess result`, e.g. `"crane:GYBBG"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s
+            .split_once(':')
+            .or_else(|| s.split_once(char::is_whitespace))
+        {
+            Some((guess, result)) => Feedback::new(guess.trim(), result.trim()),
+            None => Err(WordError::InvalidFeedbackFormat(s.to_string())),
+        }
+    }
+}
+
+/// Reads one feedback per line, e.g. `CRANE GYBBG`, to replay a whole game of words of
+/// given length. Empty lines are left out.
+pub fn read_feedback<R: BufRead>(reader: R, length: usize) -> Result<Vec<Feedback>, Error> {
+    let mut rounds = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let feedback = line
+            .parse::<Feedback>()
+            .and_then(|feedback| match feedback.guess.len() {
+                actual if actual != length => Err(WordError::InvalidWordLength {
+                    expected: length,
+                    actual,
+                }),
+                _ => Ok(feedback),
+            })
+            .map_err(|source| Error::InvalidFeedbackLine {
+                line: i + 1,
+                source,
+            })?;
+        rounds.push(feedback);
+    }
+
+    Ok(rounds)
+}
+
+/// Renders letters of `guess` on backgrounds colored like their tiles, using ANSI escape
+/// codes, for printing to a terminal.
+pub fn colorize(guess: &Word, tiles: &[Tile]) -> String {
+    let mut output = String::new();
+    for (c, tile) in guess.0.iter().zip(tiles.iter()) {
+        let color = match tile {
+            Tile::Correct => "\x1b[30;42m",
+            Tile::Present => "\x1b[30;43m",
+            Tile::Absent => "\x1b[37;100m",
+        };
+        output.push_str(&format!("{} {} ", color, c));
+    }
+    output.push_str("\x1b[0m");
+    output
+}
+
+/// Colors `guess` against `answer` following Wordle's rules for repeated letters: greens
+/// are matched first, then every other letter is yellow only while the answer still has
+/// unmatched occurrences of it, so excess repeats in the guess are grey.
+pub fn feedback(guess: &Word, answer: &Word) -> Vec<Tile> {
+    let mut tiles = vec![Tile::Absent; guess.len()];
+    let mut unmatched: Vec<char> = Vec::with_capacity(answer.len());
+
+    for (i, (guess_char, answer_char)) in guess.0.iter().zip(answer.0.iter()).enumerate() {
+        match (guess_char, answer_char) {
+            (Character::Normal(g), Character::Normal(a)) if g == a => tiles[i] = Tile::Correct,
+            (_, Character::Normal(a)) => unmatched.push(*a),
+            _ => {}
+        }
+    }
+
+    for (i, guess_char) in guess.0.iter().enumerate() {
+        if tiles[i] == Tile::Correct {
+            continue;
+        }
+
+        if let Character::Normal(g) = guess_char {
+            if let Some(index) = unmatched.iter().position(|a| a == g) {
+                unmatched.swap_remove(index);
+                tiles[i] = Tile::Present;
+            }
+        }
+    }
+
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_parse_feedback_from_guess_and_result() {
+        let actual: Feedback = "crane:GyBxG".parse().unwrap();
+        assert_eq!(actual.guess, Word::new("crane").unwrap());
+        assert_eq!(
+            actual.tiles,
+            vec![
+                Tile::Correct,
+                Tile::Present,
+                Tile::Absent,
+                Tile::Absent,
+                Tile::Correct
+            ]
+        );
+    }
+
+    #[test]
+    fn should_read_feedback_line_by_line() {
+        let input = "CRANE BYBBG\n\nhorse:BBGGG\n";
+        let actual = read_feedback(input.as_bytes(), 5).unwrap();
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[1].guess, Word::new("horse").unwrap());
+
+        let actual = read_feedback("crane GYBBG\ncrane\n".as_bytes(), 5).unwrap_err();
+        assert!(matches!(actual, Error::InvalidFeedbackLine { line: 2, .. }));
+
+        let actual = read_feedback("crane GYBBG\nplants BBBBBB\n".as_bytes(), 5).unwrap_err();
+        assert!(matches!(
+            actual,
+            Error::InvalidFeedbackLine {
+                line: 2,
+                source: WordError::InvalidWordLength {
+                    expected: 5,
+                    actual: 6
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn should_return_error_if_result_has_different_length_than_guess() {
+        let actual = Feedback::new("crane", "GYB").unwrap_err();
+        let expected = WordError::InvalidWordLength {
+            expected: 5,
+            actual: 3,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_trim_guess_and_result() {
+        let actual = Feedback::new(" crane", "GYBBG ").unwrap();
+        assert_eq!(actual.guess, Word::new("crane").unwrap());
+        assert_eq!(actual.tiles.len(), 5);
+    }
+
+    #[test]
+    fn should_return_error_if_tile_is_unknown() {
+        let actual = Feedback::new("crane", "GYBBR").unwrap_err();
+        assert_eq!(actual, WordError::InvalidTileValue('R'));
+    }
+
+    #[test]
+    fn should_color_repeated_letters_like_wordle() {
+        use Tile::*;
+
+        let actual = feedback(&Word::new("alley").unwrap(), &Word::new("level").unwrap());
+        assert_eq!(actual, vec![Absent, Present, Present, Correct, Absent]);
+
+        let actual = feedback(&Word::new("speed").unwrap(), &Word::new("abide").unwrap());
+        assert_eq!(actual, vec![Absent, Absent, Present, Absent, Present]);
+
+        let actual = feedback(&Word::new("eerie").unwrap(), &Word::new("there").unwrap());
+        assert_eq!(actual, vec![Present, Absent, Present, Absent, Correct]);
+    }
+
+    #[test]
+    fn should_display_tiles_as_letters_they_are_parsed_from() {
+        let tiles: String = feedback(&Word::new("crane").unwrap(), &Word::new("horse").unwrap())
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(tiles, "BYBBG");
+    }
+
+    #[test]
+    fn should_render_letters_on_colored_backgrounds() {
+        let guess = Word::with_length("ab", 2).unwrap();
+        let actual = colorize(&guess, &[Tile::Correct, Tile::Absent]);
+        assert_eq!(actual, "\x1b[30;42m A \x1b[37;100m B \x1b[0m");
+    }
+
+    #[test]
+    fn should_be_satisfied_only_by_words_giving_the_same_colors() {
+        let feedback = Feedback::new("crane", "BYBBG").unwrap();
+
+        assert!(feedback.is_satisfied_by(&Word::new("horse").unwrap()));
+        assert!(!feedback.is_satisfied_by(&Word::new("crane").unwrap()));
+        assert!(!feedback.is_satisfied_by(&Word::new("rinse").unwrap()));
+        assert!(!feedback.is_satisfied_by(&Word::new("crate").unwrap()));
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::Display;
+
+use crate::{Feedback, Tile};
+
+/// Guesses of a game in the order they were played, each with the colors it got and how
+/// many words were still possible after it, for reviewing the game afterwards.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    turns: Vec<(Feedback, usize)>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the next guess with its colors, and number of words still possible.
+    pub fn push(&mut self, feedback: Feedback, remaining: usize) {
+        self.turns.push((feedback, remaining));
+    }
+
+    /// Played guesses with their colors and number of words left after each of them.
+    pub fn turns(&self) -> &[(Feedback, usize)] {
+        &self.turns
+    }
+
+    pub fn len(&self) -> usize {
+        self.turns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    /// Colors of every guess as emoji squares, one row per guess, like the grid Wordle
+    /// shares after a game. Letters are left out, so it doesn't spoil the answer.
+    pub fn share_grid(&self) -> String {
+        self.turns
+            .iter()
+            .map(|(feedback, _)| {
+                feedback
+                    .tiles
+                    .iter()
+                    .map(|tile| match tile {
+                        Tile::Correct => '🟩',
+                        Tile::Present => '🟨',
+                        Tile::Absent => '⬜',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Prints a transcript with one numbered turn per line, e.g. `1. CRANE BYBBG, 12 words left`.
+/// Alternate flag (`{:#}`) prints guesses in lowercase.
+impl Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (feedback, remaining)) in self.turns.iter().enumerate() {
+            let tiles: String = feedback.tiles.iter().map(ToString::to_string).collect();
+            match f.alternate() {
+                true => write!(f, "{}. {:#} {}", i + 1, feedback.guess, tiles)?,
+                false => write!(f, "{}. {} {}", i + 1, feedback.guess, tiles)?,
+            }
+            match remaining {
+                1 => writeln!(f, ", 1 word left")?,
+                _ => writeln!(f, ", {} words left", remaining)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_print_transcript_of_all_turns() {
+        let mut history = History::new();
+        history.push(Feedback::new("crane", "BYBBG").unwrap(), 12);
+        history.push(Feedback::new("horse", "GGGGG").unwrap(), 1);
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(
+            history.to_string(),
+            "1. CRANE BYBBG, 12 words left\n2. HORSE GGGGG, 1 word left\n"
+        );
+        assert_eq!(
+            format!("{:#}", history).lines().next(),
+            Some("1. crane BYBBG, 12 words left")
+        );
+    }
+
+    #[test]
+    fn should_share_colors_as_emoji_grid() {
+        let mut history = History::new();
+        history.push(Feedback::new("crane", "BYBBG").unwrap(), 12);
+        history.push(Feedback::new("horse", "GGGGG").unwrap(), 1);
+
+        assert_eq!(history.share_grid(), "⬜🟨⬜⬜🟩\n🟩🟩🟩🟩🟩");
+        assert_eq!(History::new().share_grid(), "");
+    }
+}
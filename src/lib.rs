@@ -1,4 +1,5 @@
 use std::{
+    collections::{BTreeSet, HashMap, HashSet},
     fmt::Display,
     fs::File,
     io::{self, BufRead},
@@ -8,326 +9,3103 @@ use std::{
 
 use thiserror::Error;
 
+mod constraints;
+mod dictionary;
+mod feedback;
+mod history;
+mod multi_board;
+mod opener;
+mod pattern;
+mod rank;
+mod requirement;
+mod solver;
+
+pub use constraints::{Conflict, ConstraintStats, Constraints};
+pub use dictionary::{Dictionary, Role};
+pub use feedback::{colorize, feedback, read_feedback, Feedback, Tile};
+pub use history::History;
+pub use multi_board::MultiBoard;
+pub use opener::{read_openers, write_openers};
+pub use pattern::PatternBuilder;
+pub use rank::{EntropyStrategy, FrequencyStrategy, GuessStrategy, RankOptions, TieBreak};
+pub use requirement::Requirement;
+pub use solver::Solver;
+
+/// Length of a classic Wordle word, used when no explicit length is given.
+pub const DEFAULT_WORD_LENGTH: usize = 5;
+
+/// Chars accepted as a wildcard (unknown letter) in a word.
+pub const WILDCARDS: &[char] = &['*', '_', '?'];
+
 #[derive(Debug, Error, PartialEq)]
 pub enum WordError {
-    #[error("Word must be 5 characters long. Given word has length of '{0}'")]
-    InvalidWordLength(usize),
+    #[error("Word must be {expected} characters long. Given word has length of '{actual}'")]
+    InvalidWordLength { expected: usize, actual: usize },
     #[error("Can not parse given char '{0}' as wildcar or normal char")]
     InvalidCharValue(char),
+    #[error("Can not parse given char '{0}' as feedback tile, use 'G', 'Y' or 'B'")]
+    InvalidTileValue(char),
+    #[error("Feedback must be given as 'guess:result', got '{0}'")]
+    InvalidFeedbackFormat(String),
+    #[error("Can not parse letter set '{0}', give letters in brackets, e.g. '[st]'")]
+    InvalidLetterSet(String),
+    #[error("Position {index} is out of range for a word of length {length}")]
+    InvalidPosition { index: usize, length: usize },
+}
+
+/// Any error returned by the crate, so callers can propagate all of them with `?`.
+/// Its message is what the `words` binary prints when it fails, e.g. for a pattern of
+/// wrong length:
+///
+/// ```
+/// use words::{Error, Word};
+///
+/// let error: Error = Word::new("zow").unwrap_err().into();
+/// assert_eq!(
+///     error.to_string(),
+///     "Word must be 5 characters long. Given word has length of '3'"
+/// );
+/// ```
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Word(#[from] WordError),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("Dictionary has no words of length {0}")]
+    EmptyDictionary(usize),
+    #[error("Can not read both dictionary and guesses from stdin")]
+    StdinTakenByDictionary,
+    #[error("Invalid feedback at line {line}: {source}")]
+    InvalidFeedbackLine { line: usize, source: WordError },
+    #[error("Can not read dictionary line {line}: {source}")]
+    UnreadableLine { line: usize, source: io::Error },
+    #[error("Invalid opener at line {line}, expected a word and its score, got '{content}'")]
+    InvalidOpenerLine { line: usize, content: String },
+    #[error("Found {0} invalid dictionary lines")]
+    InvalidDictionary(usize),
+}
+
+/// Reason why a word is not possible given the chosen word and constraints.
+#[derive(Debug, Error, PartialEq)]
+pub enum RejectReason {
+    #[error(transparent)]
+    InvalidWord(#[from] WordError),
+    #[error("Word contains excluded letter '{0}'")]
+    ExcludedLetter(char),
+    #[error("Word contains letter '{letter}' more than {max} times")]
+    TooManyOccurrences { letter: char, max: usize },
+    #[error("Word does not contain included letter '{0}'")]
+    MissingIncluded(char),
+    #[error("Word does not contain '{0}'")]
+    MissingSubstring(String),
+    #[error("Word contains letter '{letter}' {found} times, expected from {min} to {max}")]
+    CountOutOfRange {
+        letter: char,
+        min: usize,
+        max: usize,
+        found: usize,
+    },
+    #[error("Word contains letter '{letter}' at position {index}, where it is known not to be")]
+    MisplacedLetter { letter: char, index: usize },
+    #[error("Letter '{letter}' at unknown position {index} is already a known letter of the word")]
+    RepeatedKnownLetter { letter: char, index: usize },
+    #[error("Expected '{expected}' at position {index}, found '{found}'")]
+    PositionMismatch {
+        index: usize,
+        expected: char,
+        found: char,
+    },
+    #[error("Expected one of '{expected}' at position {index}, found '{found}'")]
+    NotOneOf {
+        index: usize,
+        expected: String,
+        found: char,
+    },
+    #[error("Word does not match feedback of guess number {}", .0 + 1)]
+    FeedbackMismatch(usize),
+}
+
+/// Letters that can not appear anywhere in the word, wildcard positions
+/// of the chosen word included, see [`ExclusionScope`].
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Excluded {
+    letters: Vec<char>,
+    max_counts: Vec<(char, usize)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    positions: Vec<(char, Position)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    scope: ExclusionScope,
+    #[cfg_attr(feature = "serde", serde(default))]
+    unused_at_wildcards: bool,
+}
+
+/// Positions of the chosen word at which excluded letters are rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExclusionScope {
+    /// Excluded letters can not appear anywhere in the word.
+    #[default]
+    Everywhere,
+    /// Excluded letters can not fill a wildcard, but can still be fixed letters of the
+    /// chosen word, e.g. a letter that is green in one spot and grey in another.
+    Wildcards,
+}
+
+impl Excluded {
+    pub fn new(letters: Vec<char>) -> Self {
+        Self {
+            letters: unique_letters(letters),
+            max_counts: Vec::new(),
+            positions: Vec::new(),
+            scope: ExclusionScope::default(),
+            unused_at_wildcards: false,
+        }
+    }
+
+    /// Adds all letters excluded by `other` that are not excluded yet. The scope and
+    /// [`Excluded::unused_at_wildcards`] are kept.
+    pub fn merge(&mut self, other: &Excluded) -> &mut Self {
+        extend_unique(&mut self.letters, &other.letters);
+        extend_unique(&mut self.max_counts, &other.max_counts);
+        extend_unique(&mut self.positions, &other.positions);
+        self
+    }
+
+    /// Checks whether no letter is excluded in any way.
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty() && self.max_counts.is_empty() && self.positions.is_empty()
+    }
+
+    /// Checks that every position of an excluded letter is within words of given length.
+    /// Deserialized positions are not checked otherwise, see [`Position`].
+    pub fn check_positions(&self, length: usize) -> Result<(), WordError> {
+        check_positions(&self.positions, length)
+    }
+
+    /// Mask of letters that reject a word wherever they are, see [`Word::letter_mask`].
+    /// Empty when excluded letters are checked only at wildcards.
+    pub(crate) fn letter_mask(&self) -> u32 {
+        match self.scope {
+            ExclusionScope::Everywhere => letters_mask(self.letters.iter().copied()),
+            ExclusionScope::Wildcards => 0,
+        }
+    }
+
+    /// Sets at which positions of the chosen word excluded letters are rejected.
+    pub fn in_scope(mut self, scope: ExclusionScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// When `unused` is set, wildcards of the chosen word can not be filled with letters
+    /// the chosen word already has at other positions, e.g. `a???a` rejects `abaca`. By
+    /// default wildcards can be any letter.
+    pub fn unused_at_wildcards(mut self, unused: bool) -> Self {
+        self.unused_at_wildcards = unused;
+        self
+    }
+
+    /// Letter that can not appear more than `count` times, e.g. the grey second `L`
+    /// of a guess where the other `L` was green.
+    pub fn at_most(letter: char, count: usize) -> Self {
+        Self::default().and_at_most(letter, count)
+    }
+
+    /// Adds another letter that can not appear more than `count` times.
+    pub fn and_at_most(mut self, letter: char, count: usize) -> Self {
+        self.max_counts.push((to_uppercase(letter), count));
+        self
+    }
+
+    /// Letter that is not at `position`, but may still be anywhere else in the word. Unlike
+    /// [`Included::at_not`] it does not require the letter to be present.
+    pub fn at(letter: char, position: Position) -> Self {
+        Self::default().and_at(letter, position)
+    }
+
+    /// Adds another letter that is not at `position`.
+    pub fn and_at(mut self, letter: char, position: Position) -> Self {
+        self.positions.push((to_uppercase(letter), position));
+        self
+    }
+
+    fn absent_violations<'a>(
+        &'a self,
+        pattern: &'a Word,
+        word: &'a Word,
+    ) -> impl Iterator<Item = RejectReason> + 'a {
+        let positions = self.positions.iter().filter_map(move |(letter, position)| {
+            (word.0.get(position.index()) == Some(&Character::Normal(*letter))).then_some(
+                RejectReason::MisplacedLetter {
+                    letter: *letter,
+                    index: position.index(),
+                },
+            )
+        });
+
+        let letters = word.0.iter().enumerate().filter_map(move |(i, c)| {
+            let fixed = matches!(pattern.0.get(i), Some(Character::Normal(_)));
+            match c {
+                _ if fixed && self.scope == ExclusionScope::Wildcards => None,
+                Character::Normal(c) if self.letters.contains(c) => {
+                    Some(RejectReason::ExcludedLetter(*c))
+                }
+                _ => None,
+            }
+        });
+
+        let repeated = word.0.iter().enumerate().filter_map(move |(i, c)| {
+            let fixed = matches!(pattern.0.get(i), Some(Character::Normal(_)));
+            match c {
+                Character::Normal(letter)
+                    if self.unused_at_wildcards && !fixed && pattern.0.contains(c) =>
+                {
+                    Some(RejectReason::RepeatedKnownLetter {
+                        letter: *letter,
+                        index: i,
+                    })
+                }
+                _ => None,
+            }
+        });
+
+        let counts = self.max_counts.iter().filter_map(move |(letter, count)| {
+            let character = Character::Normal(*letter);
+            (word.0.iter().filter(|c| **c == character).count() > *count).then_some(
+                RejectReason::TooManyOccurrences {
+                    letter: *letter,
+                    max: *count,
+                },
+            )
+        });
+
+        positions.chain(letters).chain(repeated).chain(counts)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Included {
+    letters: Vec<char>,
+    misplaced: Vec<(char, Position)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    counts: Vec<(char, usize, usize)>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    substrings: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    likely: Vec<(char, f64)>,
+}
+
+impl Included {
+    pub fn new(letters: Vec<char>) -> Self {
+        Self {
+            letters: unique_letters(letters),
+            misplaced: Vec::new(),
+            counts: Vec::new(),
+            substrings: Vec::new(),
+            likely: Vec::new(),
+        }
+    }
+
+    /// Adds all letters included by `other` that are not included yet.
+    pub fn merge(&mut self, other: &Included) -> &mut Self {
+        extend_unique(&mut self.letters, &other.letters);
+        extend_unique(&mut self.misplaced, &other.misplaced);
+        extend_unique(&mut self.counts, &other.counts);
+        extend_unique(&mut self.substrings, &other.substrings);
+        extend_unique(&mut self.likely, &other.likely);
+        self
+    }
+
+    /// Checks whether no letter is included in any way.
+    pub fn is_empty(&self) -> bool {
+        self.letters.is_empty()
+            && self.misplaced.is_empty()
+            && self.counts.is_empty()
+            && self.substrings.is_empty()
+    }
+
+    /// Checks that every position of a misplaced letter is within words of given length.
+    /// Deserialized positions are not checked otherwise, see [`Position`].
+    pub fn check_positions(&self, length: usize) -> Result<(), WordError> {
+        check_positions(&self.misplaced, length)
+    }
+
+    /// Mask of letters every matching word has to contain, see [`Word::letter_mask`].
+    pub(crate) fn letter_mask(&self) -> u32 {
+        let misplaced = self.misplaced.iter().map(|(letter, _)| letter);
+        let counted = self
+            .counts
+            .iter()
+            .filter(|(_, min, _)| *min > 0)
+            .map(|(letter, _, _)| letter);
+        let substrings = self.substrings.iter().flat_map(|s| s.chars());
+        letters_mask(
+            self.letters
+                .iter()
+                .chain(misplaced)
+                .chain(counted)
+                .copied()
+                .chain(substrings),
+        )
+    }
+
+    /// Letter that is in the word but not at `position` (a yellow tile).
+    pub fn at_not(letter: char, position: Position) -> Self {
+        Self::default().and_at_not(letter, position)
+    }
+
+    /// Adds another letter that is in the word but not at `position`.
+    pub fn and_at_not(mut self, letter: char, position: Position) -> Self {
+        self.misplaced.push((to_uppercase(letter), position));
+        self
+    }
+
+    /// Letter that appears at least `min` and at most `max` times, e.g. exactly two
+    /// `L`s with `Included::count('l', 2, 2)`.
+    pub fn count(letter: char, min: usize, max: usize) -> Self {
+        Self::default().and_count(letter, min, max)
+    }
+
+    /// Adds another letter that appears at least `min` and at most `max` times.
+    pub fn and_count(mut self, letter: char, min: usize, max: usize) -> Self {
+        self.counts.push((to_uppercase(letter), min, max));
+        self
+    }
+
+    /// Letters that appear next to each other, in this order, somewhere in the word, e.g.
+    /// `ST` in both `TOAST` and `STORE`.
+    pub fn contains(substring: &str) -> Self {
+        Self::default().and_contains(substring)
+    }
+
+    /// Adds another group of letters that appear next to each other.
+    pub fn and_contains(mut self, substring: &str) -> Self {
+        self.substrings
+            .push(substring.chars().map(to_uppercase).collect());
+        self
+    }
+
+    /// Letter that is probably, but not surely, in the word, e.g. from a yellow tile you
+    /// are not sure about. Words without it still match, but words with it rank `weight`
+    /// times higher, see [`RankOptions::letter_weights`]. Weight of 1.0 changes nothing.
+    pub fn likely(letter: char, weight: f64) -> Self {
+        Self::default().and_likely(letter, weight)
+    }
+
+    /// Adds another letter that is probably in the word.
+    pub fn and_likely(mut self, letter: char, weight: f64) -> Self {
+        self.likely.push((to_uppercase(letter), weight));
+        self
+    }
+
+    /// Letters added with [`Included::likely`] and their weights.
+    pub fn likely_letters(&self) -> &[(char, f64)] {
+        &self.likely
+    }
+
+    fn present_violations<'a>(&'a self, word: &'a Word) -> impl Iterator<Item = RejectReason> + 'a {
+        let misplaced = self.misplaced.iter().map(|(letter, _)| letter);
+        let letters = self
+            .letters
+            .iter()
+            .chain(misplaced)
+            .filter(move |letter| !word.0.contains(&Character::Normal(**letter)))
+            .map(|letter| RejectReason::MissingIncluded(*letter));
+
+        let counts = self.counts.iter().filter_map(move |(letter, min, max)| {
+            let character = Character::Normal(*letter);
+            let found = word.0.iter().filter(|c| **c == character).count();
+            (found < *min || found > *max).then_some(RejectReason::CountOutOfRange {
+                letter: *letter,
+                min: *min,
+                max: *max,
+                found,
+            })
+        });
+
+        let substrings = self
+            .substrings
+            .iter()
+            .filter(move |substring| !word.to_string().contains(substring.as_str()))
+            .map(|substring| RejectReason::MissingSubstring(substring.clone()));
+
+        letters.chain(counts).chain(substrings)
+    }
+
+    fn misplaced_violations<'a>(
+        &'a self,
+        word: &'a Word,
+    ) -> impl Iterator<Item = RejectReason> + 'a {
+        self.misplaced.iter().filter_map(move |(letter, position)| {
+            (word.0.get(position.index()) == Some(&Character::Normal(*letter))).then_some(
+                RejectReason::MisplacedLetter {
+                    letter: *letter,
+                    index: position.index(),
+                },
+            )
+        })
+    }
+}
+
+impl FromStr for Included {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Included::new(parse_letters(s)?))
+    }
+}
+
+impl FromStr for Excluded {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Excluded::new(parse_letters(s)?))
+    }
+}
+
+/// Merges all sets into one, leaving out letters that repeat.
+impl FromIterator<Excluded> for Excluded {
+    fn from_iter<I: IntoIterator<Item = Excluded>>(iter: I) -> Self {
+        let mut merged = Excluded::default();
+        for excluded in iter {
+            merged.merge(&excluded);
+        }
+        merged
+    }
+}
+
+/// Merges all sets into one, leaving out letters that repeat.
+impl FromIterator<Included> for Included {
+    fn from_iter<I: IntoIterator<Item = Included>>(iter: I) -> Self {
+        let mut merged = Included::default();
+        for included in iter {
+            merged.merge(&included);
+        }
+        merged
+    }
+}
+
+fn extend_unique<T: PartialEq + Clone>(items: &mut Vec<T>, other: &[T]) {
+    for item in other {
+        if !items.contains(item) {
+            items.push(item.clone());
+        }
+    }
+}
+
+/// Uppercase letters in the order they are given, each only once.
+fn unique_letters(letters: Vec<char>) -> Vec<char> {
+    let mut unique = Vec::with_capacity(letters.len());
+    for letter in letters.into_iter().map(to_uppercase) {
+        if !unique.contains(&letter) {
+            unique.push(letter);
+        }
+    }
+    unique
+}
+
+fn parse_letters(s: &str) -> Result<Vec<char>, WordError> {
+    s.chars()
+        .map(|c| match c {
+            c if c.is_alphabetic() => Ok(c),
+            _ => Err(WordError::InvalidCharValue(c)),
+        })
+        .collect()
+}
+
+/// Words are ordered character by character, see [`Character`], so words of letters only
+/// are in alphabetical order of their uppercase forms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Word(Vec<Character>);
+
+impl Word {
+    pub fn new(word: &str) -> Result<Self, WordError> {
+        let output: Word = word.parse()?;
+        Ok(output)
+    }
+
+    /// Parses a word that must be exactly `length` characters long. Characters are counted
+    /// as chars, so a letter followed by a combining accent counts as two: words have to be
+    /// written with precomposed letters, like `é`, to match. A set of letters in brackets,
+    /// e.g. `[st]`, counts as a single character. Surrounding whitespace, like a trailing
+    /// `\r` or `\n` of a line, is ignored.
+    pub fn with_length(word: &str, length: usize) -> Result<Self, WordError> {
+        Self::with_class(word, length, CharClass::default())
+    }
+
+    /// Same as [`Word::with_length`], but accepts as letters only chars of given `class`.
+    pub fn with_class(word: &str, length: usize, class: CharClass) -> Result<Self, WordError> {
+        Self::from_char_iter(word.trim().chars(), length, class)
+    }
+
+    /// Builds a word from already split chars, e.g. `['c', 'r', '?', 'n', 'e']`.
+    pub fn from_chars(chars: &[char]) -> Result<Self, WordError> {
+        Self::from_char_iter(
+            chars.iter().copied(),
+            DEFAULT_WORD_LENGTH,
+            CharClass::default(),
+        )
+    }
+
+    fn from_char_iter(
+        mut chars: impl Iterator<Item = char>,
+        length: usize,
+        class: CharClass,
+    ) -> Result<Self, WordError> {
+        let mut characters: Vec<Result<Character, WordError>> = Vec::with_capacity(length);
+
+        while let Some(c) = chars.next() {
+            match c {
+                '[' => characters.push(parse_letter_set(&mut chars, class)),
+                c => characters.push(Character::with_class(c, class)),
+            }
+        }
+
+        if characters.len() != length {
+            return Err(WordError::InvalidWordLength {
+                expected: length,
+                actual: characters.len(),
+            });
+        };
+
+        Ok(Word(characters.into_iter().collect::<Result<_, _>>()?))
+    }
+
+    /// Uppercase form of the word, with spaces in place of wildcards.
+    pub fn as_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Same as `to_string`, but with letters in lowercase.
+    pub fn to_lowercase_string(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Number of letters known at their positions, wildcards and letter sets left out.
+    pub fn known_letters(&self) -> usize {
+        self.0
+            .iter()
+            .filter(|c| matches!(c, Character::Normal(_)))
+            .count()
+    }
+
+    /// Bit `i` is set when the word contains the `i`-th letter of the English alphabet,
+    /// so words can be compared with constraints by bitwise operations. Other letters,
+    /// wildcards and letter sets are left out.
+    pub fn letter_mask(&self) -> u32 {
+        letters_mask(self.0.iter().filter_map(|c| match c {
+            Character::Normal(c) => Some(*c),
+            _ => None,
+        }))
+    }
+
+    /// Checks whether the word has the same length as `pattern` and the pattern's letter,
+    /// or one of its letter set, at every position. Wildcards of the pattern match any
+    /// character. Unlike `==`, `"c*t"` matches `"cat"`.
+    pub fn matches_pattern(&self, pattern: &Word) -> bool {
+        self.len() == pattern.len() && fixed_position_violations(pattern, self).next().is_none()
+    }
+
+    /// Iterates over characters of the word, in order.
+    pub fn chars(&self) -> impl Iterator<Item = &Character> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Words matching a chosen word (pattern) and constraints.
+///
+/// Candidates are added with [`WordsResult::is_word_possible`], usually once for every
+/// dictionary line. When more is learned about the answer, [`WordsResult::refine`] narrows
+/// the words found so far instead of checking the whole dictionary again, and
+/// [`WordsResult::reset`] clears them before starting over with other constraints.
+#[derive(Debug, Clone)]
+pub struct WordsResult {
+    chosen_word: Word,
+    feedback: Vec<Feedback>,
+    skipped: usize,
+    checked: usize,
+    weights: HashMap<Word, u32>,
+    pub possible_words: Vec<Word>,
 }
 
-#[derive(Debug)]
-pub struct Excluded(pub Vec<char>);
+impl<'a> WordsResult {
+    pub fn new(chosen_word: Word) -> Self {
+        Self {
+            chosen_word,
+            feedback: Vec::new(),
+            skipped: 0,
+            checked: 0,
+            weights: HashMap::new(),
+            possible_words: Vec::new(),
+        }
+    }
+
+    /// Words that matched so far.
+    pub fn words(&self) -> &[Word] {
+        &self.possible_words
+    }
+
+    /// Number of words that matched so far.
+    pub fn remaining(&self) -> usize {
+        self.possible_words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.possible_words.is_empty()
+    }
+
+    /// Orders possible words alphabetically by their uppercase form.
+    pub fn sort(&mut self) {
+        self.possible_words
+            .sort_by_cached_key(|word| word.to_string());
+    }
+
+    /// Weight the possible word was loaded with, 1 when none was given.
+    pub fn weight(&self, word: &Word) -> u32 {
+        self.weights.get(word).copied().unwrap_or(1)
+    }
+
+    /// Possible words ordered from the best next guess, scored by how common their
+    /// letters are at each position among all possible words, times their weight.
+    /// Words with equal scores are in alphabetical order.
+    pub fn ranked(&self) -> Vec<(&Word, f64)> {
+        self.ranked_with(&RankOptions::default())
+    }
+
+    /// Same as [`WordsResult::ranked`], with words of equal scores ordered as `options` say.
+    pub fn ranked_with(&self, options: &RankOptions) -> Vec<(&Word, f64)> {
+        self.ranked_by(&FrequencyStrategy, options)
+    }
+
+    /// Possible words ordered from the best next guess, scored by `strategy` against all
+    /// possible words, times their weight and weights of letters given in `options`.
+    pub fn ranked_by<S: GuessStrategy + ?Sized>(
+        &self,
+        strategy: &S,
+        options: &RankOptions,
+    ) -> Vec<(&Word, f64)> {
+        let candidates: Vec<&Word> = self.possible_words.iter().collect();
+        self.ranked_guesses_by(&candidates, strategy, options)
+    }
+
+    /// Same as [`WordsResult::ranked_by`], but ranks `guesses` instead, e.g. words that are
+    /// only allowed as guesses, while still scoring them against all possible words.
+    pub fn ranked_guesses_by<'w, S: GuessStrategy + ?Sized>(
+        &'w self,
+        guesses: &[&'w Word],
+        strategy: &S,
+        options: &RankOptions,
+    ) -> Vec<(&'w Word, f64)> {
+        let candidates: Vec<&Word> = self.possible_words.iter().collect();
+        let scores = strategy.score_all(guesses, &candidates);
+        let mut ranked: Vec<(&Word, f64)> = guesses
+            .iter()
+            .zip(scores)
+            .map(|(word, score)| {
+                let score = score * options.letter_bonus(word);
+                (*word, score * self.weight(word) as f64)
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            b.1.total_cmp(&a.1).then_with(|| {
+                let a = (a.0, self.weight(a.0));
+                let b = (b.0, self.weight(b.0));
+                options.tie_break.compare(a, b)
+            })
+        });
+        ranked
+    }
+
+    /// Possible word that splits possible words into the most, and most even, groups by the
+    /// feedback it would get, so the guess may still win this turn. When several words are
+    /// equally good, the alphabetically first one is picked.
+    pub fn best_candidate_guess(&self) -> Option<&Word> {
+        let candidates: Vec<&Word> = self.possible_words.iter().collect();
+        self.possible_words
+            .iter()
+            .map(|word| (word, rank::entropy(word, &candidates)))
+            .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(word, _)| word)
+    }
+
+    /// Any word of `dictionary` that splits possible words the most, e.g. a word already
+    /// ruled out that tells more than every possible word, see [`Dictionary::best_guess`].
+    pub fn best_guess<'d>(&self, dictionary: &'d Dictionary) -> Option<&'d Word> {
+        let candidates: Vec<&Word> = self.possible_words.iter().collect();
+        dictionary.best_guess(&candidates)
+    }
+
+    /// How much of the checked words are ruled out, from 0.0 when all of them are still
+    /// possible (or none were checked) to 1.0 when none are.
+    pub fn specificity(&self) -> f64 {
+        if self.checked == 0 {
+            return 0.0;
+        }
+        1.0 - self.remaining() as f64 / self.checked as f64
+    }
+
+    /// Bits of information still missing to single out the answer, 0.0 once there
+    /// is at most one possible word.
+    pub fn remaining_bits(&self) -> f64 {
+        (self.remaining().max(1) as f64).log2()
+    }
+
+    /// Number of candidates of the right length checked with [`WordsResult::is_word_possible`],
+    /// whether they matched or not.
+    pub fn checked(&self) -> usize {
+        self.checked
+    }
+
+    /// Number of checked words that were skipped because they contain invalid chars.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Checks whether nothing is known: the chosen word is all wildcards and there are
+    /// no constraints nor feedback, so every word of the right length is possible.
+    pub fn is_unconstrained(&self, excluded: &Excluded, included: &Included) -> bool {
+        self.chosen_word.chars().all(Character::is_wildcard)
+            && self.feedback.is_empty()
+            && excluded.is_empty()
+            && included.is_empty()
+    }
+
+    /// Indices of the chosen word that are wildcards, i.e. where the letter is not known
+    /// yet. Letter sets are only partly known, so they are not included.
+    pub fn unknown_positions(&self) -> Vec<usize> {
+        self.chosen_word
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| c.is_wildcard())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// For every position of the chosen word, letters that any possible word has there.
+    /// Render it with [`PositionGrid`].
+    pub fn position_candidates(&self) -> Vec<BTreeSet<char>> {
+        let mut positions = vec![BTreeSet::new(); self.chosen_word.len()];
+        for word in self.possible_words.iter() {
+            for (letters, c) in positions.iter_mut().zip(word.0.iter()) {
+                if let Character::Normal(c) = c {
+                    letters.insert(*c);
+                }
+            }
+        }
+        positions
+    }
+
+    /// Adds a round of feedback that every possible word has to be consistent with.
+    /// Fails when the guess has different length than the chosen word.
+    pub fn add_feedback(&mut self, feedback: Feedback) -> Result<(), WordError> {
+        if feedback.guess.len() != self.chosen_word.len() {
+            return Err(WordError::InvalidWordLength {
+                expected: self.chosen_word.len(),
+                actual: feedback.guess.len(),
+            });
+        }
+        self.feedback.push(feedback);
+        Ok(())
+    }
+
+    /// Checks `target` and keeps it as a possible word if it matches. Words that are
+    /// already possible, e.g. repeated dictionary lines, are kept only once. The target
+    /// can be followed by its weight, see [`split_weight`].
+    pub fn is_word_possible(
+        &mut self,
+        target: &'a str,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> bool {
+        let (target, weight) = split_weight(target);
+        let target_word = match Word::with_length(target, self.chosen_word.len()) {
+            Ok(word) => word,
+            Err(WordError::InvalidWordLength { .. }) => return false,
+            Err(_) => {
+                self.skipped += 1;
+                return false;
+            }
+        };
+        let masks = (excluded.letter_mask(), included.letter_mask());
+        self.add_if_possible(&target_word, weight, masks, excluded, included)
+    }
+
+    /// Same as [`WordsResult::is_word_possible`] for every one of already parsed
+    /// `candidates`, e.g. words of a [`Dictionary`], so they are not parsed again. Words
+    /// of different length than the chosen word are left out. Every word gets weight 1,
+    /// see [`WordsResult::filter_weighted`] to keep weights. Returns number of words that
+    /// are possible.
+    pub fn filter_slice(
+        &mut self,
+        candidates: &[Word],
+        excluded: &Excluded,
+        included: &Included,
+    ) -> usize {
+        self.filter_weighted(candidates.iter().map(|word| (word, 1)), excluded, included)
+    }
+
+    /// Same as [`WordsResult::filter_slice`] for candidates together with their weights,
+    /// e.g. from [`Dictionary::weighted_words`].
+    pub fn filter_weighted<'w>(
+        &mut self,
+        candidates: impl IntoIterator<Item = (&'w Word, u32)>,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> usize {
+        let masks = (excluded.letter_mask(), included.letter_mask());
+        for (word, weight) in candidates {
+            if word.len() == self.chosen_word.len() {
+                self.add_if_possible(word, weight, masks, excluded, included);
+            }
+        }
+        self.remaining()
+    }
+
+    /// Adds `word` if it matches, `masks` are letter masks of the excluded and included
+    /// letters, so most words are rejected before checking them letter by letter.
+    fn add_if_possible(
+        &mut self,
+        word: &Word,
+        weight: u32,
+        (excluded_mask, included_mask): (u32, u32),
+        excluded: &Excluded,
+        included: &Included,
+    ) -> bool {
+        self.checked += 1;
+
+        if !mask_allows(word.letter_mask(), excluded_mask, included_mask)
+            || !matches(&self.chosen_word, word, excluded, included)
+            || !self.satisfies_feedback(word)
+        {
+            return false;
+        }
+
+        if !self.weights.contains_key(word) {
+            self.weights.insert(word.clone(), weight);
+            self.possible_words.push(word.clone());
+        }
+        true
+    }
+
+    /// Leaves out possible words that don't match the new constraints, or any feedback
+    /// added since they were checked. Returns number of words that are left.
+    pub fn refine(&mut self, excluded: &Excluded, included: &Included) -> usize {
+        for word in std::mem::take(&mut self.possible_words) {
+            match self.check_word(&word, excluded, included) {
+                Ok(()) => self.possible_words.push(word),
+                Err(_) => {
+                    self.weights.remove(&word);
+                }
+            }
+        }
+        self.remaining()
+    }
+
+    /// Words possible in both results, e.g. for two boards solved at once, in order
+    /// of this result.
+    pub fn intersect(&self, other: &WordsResult) -> Vec<Word> {
+        let other: HashSet<&Word> = other.possible_words.iter().collect();
+        self.possible_words
+            .iter()
+            .filter(|word| other.contains(word))
+            .cloned()
+            .collect()
+    }
+
+    /// Forgets all possible and skipped words, keeping the chosen word and feedback.
+    pub fn reset(&mut self) {
+        self.possible_words.clear();
+        self.weights.clear();
+        self.skipped = 0;
+        self.checked = 0;
+    }
+
+    /// Explains why `target` is not a possible word, or returns `Ok` when it is.
+    pub fn check(
+        &self,
+        target: &str,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> Result<(), RejectReason> {
+        let target = Word::with_length(target, self.chosen_word.len())?;
+        self.check_word(&target, excluded, included)
+    }
+
+    /// Returns candidates that match the chosen word and constraints without storing them.
+    /// Candidates that can not be parsed as words are left out.
+    pub fn matches(
+        &'a self,
+        candidates: impl Iterator<Item = &'a str> + 'a,
+        excluded: &'a Excluded,
+        included: &'a Included,
+    ) -> impl Iterator<Item = Word> + 'a {
+        candidates
+            .filter_map(|c| Word::with_length(split_weight(c).0, self.chosen_word.len()).ok())
+            .filter(move |word| self.check_word(word, excluded, included).is_ok())
+    }
+
+    /// Candidates that match only the chosen word's letters and wildcards, ignoring any
+    /// excluded/included constraints and feedback, e.g. for crossword lookups. Candidates
+    /// that can not be parsed as words are left out.
+    pub fn pattern_matches(&self, candidates: impl Iterator<Item = &'a str>) -> Vec<Word> {
+        candidates
+            .filter_map(|c| Word::with_length(split_weight(c).0, self.chosen_word.len()).ok())
+            .filter(|word| word.matches_pattern(&self.chosen_word))
+            .collect()
+    }
+
+    /// Candidates that fail at most `max_violations` constraints, or feedback rounds,
+    /// together with the reasons they fail, fewest first. Words that match are left out.
+    /// Useful when nothing matches, to spot a wrongly entered color.
+    pub fn near_misses(
+        &self,
+        candidates: impl Iterator<Item = &'a str>,
+        excluded: &Excluded,
+        included: &Included,
+        max_violations: usize,
+    ) -> Vec<(Word, Vec<RejectReason>)> {
+        let mut near_misses: Vec<(Word, Vec<RejectReason>)> = candidates
+            .filter_map(|c| Word::with_length(split_weight(c).0, self.chosen_word.len()).ok())
+            .filter_map(|word| {
+                let reasons = self.reasons(&word, excluded, included);
+                match reasons.len() {
+                    0 => None,
+                    n if n > max_violations => None,
+                    _ => Some((word, reasons)),
+                }
+            })
+            .collect();
+
+        near_misses.sort_by_key(|(_, reasons)| reasons.len());
+        near_misses
+    }
+
+    /// Every reason why `target` is not a possible word, empty if it is. Unlike
+    /// [`WordsResult::check`] it doesn't stop at the first failed constraint, e.g. for
+    /// explaining why a word is left out.
+    pub fn explain(
+        &self,
+        target: &str,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> Result<Vec<RejectReason>, WordError> {
+        let target = Word::with_length(target, self.chosen_word.len())?;
+        Ok(self.reasons(&target, excluded, included))
+    }
+
+    /// Every constraint and feedback round `target` is checked against, each with whether
+    /// `target` passes it, e.g. to show what rules a word out and what it fulfils.
+    pub fn checklist(
+        &self,
+        target: &str,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> Result<Vec<(Requirement, bool)>, WordError> {
+        let target = Word::with_length(target, self.chosen_word.len())?;
+        let reasons = self.reasons(&target, excluded, included);
+        let requirements =
+            requirement::requirements(&self.chosen_word, excluded, included, self.feedback.len());
+
+        Ok(requirements
+            .into_iter()
+            .map(|requirement| {
+                let passed = !reasons.iter().any(|r| requirement.is_failed_by(r));
+                (requirement, passed)
+            })
+            .collect())
+    }
+
+    fn reasons(
+        &self,
+        target: &Word,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> Vec<RejectReason> {
+        let mut reasons: Vec<RejectReason> = Vec::new();
+        for reason in violations(&self.chosen_word, target, excluded, included) {
+            if !reasons.contains(&reason) {
+                reasons.push(reason);
+            }
+        }
+        for (round, feedback) in self.feedback.iter().enumerate() {
+            if !feedback.is_satisfied_by(target) {
+                reasons.push(RejectReason::FeedbackMismatch(round));
+            }
+        }
+        reasons
+    }
+
+    fn satisfies_feedback(&self, target: &Word) -> bool {
+        self.feedback
+            .iter()
+            .all(|feedback| feedback.is_satisfied_by(target))
+    }
+
+    fn check_word(
+        &self,
+        target: &Word,
+        excluded: &Excluded,
+        included: &Included,
+    ) -> Result<(), RejectReason> {
+        check(&self.chosen_word, target, excluded, included)?;
+
+        for (round, feedback) in self.feedback.iter().enumerate() {
+            if !feedback.is_satisfied_by(target) {
+                return Err(RejectReason::FeedbackMismatch(round));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks `target` against the letters of `pattern` and the excluded/included constraints.
+/// Words of different length than `pattern` never match.
+/// Constraints are applied in order: fixed positions, letters that must be present,
+/// positions banned for present letters and finally absent letters.
+pub(crate) fn check(
+    pattern: &Word,
+    target: &Word,
+    excluded: &Excluded,
+    included: &Included,
+) -> Result<(), RejectReason> {
+    if pattern.len() != target.len() {
+        return Err(RejectReason::InvalidWord(WordError::InvalidWordLength {
+            expected: pattern.len(),
+            actual: target.len(),
+        }));
+    }
+
+    match violations(pattern, target, excluded, included).next() {
+        Some(reason) => Err(reason),
+        None => Ok(()),
+    }
+}
+
+/// Every reason why `target` does not match, in the order constraints are checked in.
+pub(crate) fn violations<'a>(
+    pattern: &'a Word,
+    target: &'a Word,
+    excluded: &'a Excluded,
+    included: &'a Included,
+) -> impl Iterator<Item = RejectReason> + 'a {
+    fixed_position_violations(pattern, target)
+        .chain(included.present_violations(target))
+        .chain(included.misplaced_violations(target))
+        .chain(excluded.absent_violations(pattern, target))
+}
+
+fn fixed_position_violations<'a>(
+    pattern: &'a Word,
+    target: &'a Word,
+) -> impl Iterator<Item = RejectReason> + 'a {
+    pattern
+        .0
+        .iter()
+        .zip(target.0.iter())
+        .enumerate()
+        .filter_map(|(index, (pattern_char, target_char))| {
+            let found = match target_char {
+                Character::Normal(c) => *c,
+                _ => '*',
+            };
+
+            match pattern_char {
+                Character::Normal(expected) if *expected != found => {
+                    Some(RejectReason::PositionMismatch {
+                        index,
+                        expected: *expected,
+                        found,
+                    })
+                }
+                Character::OneOf(letters) if !letters.contains(&found) => {
+                    Some(RejectReason::NotOneOf {
+                        index,
+                        expected: letters.iter().collect(),
+                        found,
+                    })
+                }
+                _ => None,
+            }
+        })
+}
+
+/// Checks whether `candidate` matches `pattern` and the excluded/included constraints,
+/// without storing it anywhere. Same as [`WordsResult::is_word_possible`] for a result
+/// with no feedback rounds.
+pub fn matches(pattern: &Word, candidate: &Word, excluded: &Excluded, included: &Included) -> bool {
+    check(pattern, candidate, excluded, included).is_ok()
+}
+
+/// Quick check of a word's [`Word::letter_mask`] against the excluded/included letters,
+/// `false` means the word surely does not match, but `true` doesn't mean it does.
+pub(crate) fn mask_allows(mask: u32, excluded: u32, included: u32) -> bool {
+    mask & excluded == 0 && mask & included == included
+}
+
+fn letters_mask(letters: impl Iterator<Item = char>) -> u32 {
+    letters
+        .filter(|c| c.is_ascii_uppercase())
+        .fold(0, |mask, c| mask | 1 << (c as u8 - b'A'))
+}
+
+/// Returns a closure for [`Iterator::filter`] that keeps candidates matching `pattern`
+/// and the excluded/included constraints, e.g.
+/// `words.iter().copied().filter(constraint_predicate(&pattern, &excluded, &included))`.
+/// Candidates that can not be parsed as words of the pattern's length are left out.
+pub fn constraint_predicate<'a>(
+    pattern: &'a Word,
+    excluded: &'a Excluded,
+    included: &'a Included,
+) -> impl Fn(&&str) -> bool + 'a {
+    move |candidate| match Word::with_length(split_weight(candidate).0, pattern.len()) {
+        Ok(word) => matches(pattern, &word, excluded, included),
+        Err(_) => false,
+    }
+}
+
+/// Letters possible at every position, see [`WordsResult::position_candidates`].
+pub struct PositionGrid<'a>(pub &'a [BTreeSet<char>]);
+
+/// Prints a grid with a column for every position, counted from 1, and a row for every
+/// letter possible anywhere, with `#` where the letter is possible and `.` elsewhere.
+/// Positions with a single `#` are nearly determined.
+impl Display for PositionGrid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let letters: BTreeSet<&char> = self.0.iter().flatten().collect();
+
+        write!(f, " ")?;
+        for position in 1..=self.0.len() {
+            write!(f, " {}", position % 10)?;
+        }
+        writeln!(f)?;
+
+        for letter in letters {
+            write!(f, "{}", letter)?;
+            for position in self.0.iter() {
+                match position.contains(letter) {
+                    true => write!(f, " #")?,
+                    false => write!(f, " .")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Results are equal when they have the same chosen word and the same possible words,
+/// in any order.
+impl PartialEq for WordsResult {
+    fn eq(&self, other: &Self) -> bool {
+        self.chosen_word == other.chosen_word
+            && self.possible_words.len() == other.possible_words.len()
+            && self.possible_words.iter().collect::<HashSet<_>>()
+                == other.possible_words.iter().collect::<HashSet<_>>()
+    }
+}
+
+impl Eq for WordsResult {}
+
+/// Precision limits how many words are listed, e.g. `format!("{:.10}", result)`,
+/// and alternate flag (`{:#}`) lists them in lowercase. A footer tells how many of the
+/// checked candidates matched.
+impl Display for WordsResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = f.precision().unwrap_or(self.possible_words.len());
+
+        writeln!(f, "List of possible matching words:")?;
+        for (i, word) in self.possible_words.iter().take(limit).enumerate() {
+            match f.alternate() {
+                true => writeln!(f, "{}. {:#}", i + 1, word)?,
+                false => writeln!(f, "{}. {}", i + 1, word)?,
+            }
+        }
+
+        if self.possible_words.len() > limit {
+            writeln!(f, "... and {} more", self.possible_words.len() - limit)?;
+        }
+
+        if self.checked > 0 {
+            writeln!(f, "Matched {} of {} words", self.remaining(), self.checked)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Word {
+    type Err = WordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Word::with_length(s, DEFAULT_WORD_LENGTH)
+    }
+}
+
+/// Serializes as an uppercase string with `*` in place of wildcards and letter sets in
+/// brackets, so it can be read back whatever its length.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Word {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let word: String = self
+            .0
+            .iter()
+            .map(|c| match c {
+                Character::Wildcard => "*".to_string(),
+                c => c.to_string(),
+            })
+            .collect();
+        serializer.serialize_str(&word)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Word {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let word = String::deserialize(deserializer)?;
+        Word::with_length(&word, pattern_length(&word)).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Number of positions in `pattern`, counting every letter set in brackets as one.
+#[cfg(feature = "serde")]
+fn pattern_length(pattern: &str) -> usize {
+    let mut in_set = false;
+    pattern
+        .trim()
+        .chars()
+        .filter(|c| match c {
+            '[' if !in_set => {
+                in_set = true;
+                true
+            }
+            ']' if in_set => {
+                in_set = false;
+                false
+            }
+            _ => !in_set,
+        })
+        .count()
+}
+
+/// Index of a letter in a word, counted from 0 and checked to be within the word's length.
+/// A deserialized position does not know the length, so constraints holding it should be
+/// checked with e.g. [`Excluded::check_positions`] before they are applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position(u8);
+
+impl Position {
+    /// Creates position `index` in a word of given `length`.
+    pub fn new(index: usize, length: usize) -> Result<Self, WordError> {
+        match u8::try_from(index) {
+            Ok(position) if index < length => Ok(Self(position)),
+            _ => Err(WordError::InvalidPosition { index, length }),
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+fn check_positions(positions: &[(char, Position)], length: usize) -> Result<(), WordError> {
+    for (_, position) in positions.iter() {
+        Position::new(position.index(), length)?;
+    }
+    Ok(())
+}
+
+/// Letters are ordered by their uppercase chars and before wildcards, which are before
+/// letter sets.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Character {
+    /// Known letter, always stored uppercase.
+    Normal(char),
+    /// Unknown letter, written as any of [`WILDCARDS`].
+    Wildcard,
+    /// Any of a few letters, written in brackets, e.g. `[st]`. Always stored uppercase.
+    OneOf(Vec<char>),
+}
+
+impl Character {
+    /// Parses a wildcard or a letter, accepting as letters only chars of given `class`.
+    /// Wildcards and brackets are never letters.
+    pub fn with_class(c: char, class: CharClass) -> Result<Self, WordError> {
+        match c {
+            c if WILDCARDS.contains(&c) => Ok(Self::Wildcard),
+            '[' | ']' => Err(WordError::InvalidCharValue(c)),
+            c if class.contains(c) => Ok(Self::Normal(to_uppercase(c))),
+            _ => Err(WordError::InvalidCharValue(c)),
+        }
+    }
+
+    pub fn is_wildcard(&self) -> bool {
+        *self == Character::Wildcard
+    }
+}
+
+/// Alternate flag (`{:#}`) prints letters in lowercase.
+impl Display for Character {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let letter = |c: &char| match f.alternate() {
+            true => to_lowercase(*c),
+            false => *c,
+        };
+
+        match self {
+            Character::Normal(v) => write!(f, "{}", letter(v)),
+            Character::Wildcard => write!(f, " "),
+            Character::OneOf(letters) => {
+                write!(f, "[{}]", letters.iter().map(letter).collect::<String>())
+            }
+        }
+    }
+}
+
+/// Alternate flag (`{:#}`) prints letters in lowercase.
+impl Display for Word {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.iter() {
+            match f.alternate() {
+                true => write!(f, "{:#}", c)?,
+                false => write!(f, "{}", c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<char> for Character {
+    type Error = WordError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Self::with_class(value, CharClass::default())
+    }
+}
+
+/// Chars accepted as letters of a word, i.e. as [`Character::Normal`].
+#[derive(Debug, Clone, Copy, Default)]
+pub enum CharClass {
+    /// Alphabetic chars of any script.
+    #[default]
+    Alphabetic,
+    /// Only ASCII letters, `a` to `z` in any case.
+    Ascii,
+    /// Chars for which the function returns `true`, e.g. letters and hyphens for
+    /// crossword entries.
+    Custom(fn(char) -> bool),
+}
+
+impl CharClass {
+    pub fn contains(self, c: char) -> bool {
+        match self {
+            Self::Alphabetic => c.is_alphabetic(),
+            Self::Ascii => c.is_ascii_alphabetic(),
+            Self::Custom(is_letter) => is_letter(c),
+        }
+    }
+}
+
+/// Uppercase form of a letter, the one stored and compared everywhere. Only ASCII letters
+/// are changed when the default `unicode` feature is disabled, otherwise e.g. `é` matches `É`.
+/// Letters whose uppercase form is more than one char, like `ß`, are kept as they are.
+pub(crate) fn to_uppercase(c: char) -> char {
+    #[cfg(feature = "unicode")]
+    {
+        let mut upper = c.to_uppercase();
+        match (upper.next(), upper.next()) {
+            (Some(upper), None) => upper,
+            _ => c,
+        }
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    c.to_ascii_uppercase()
+}
+
+/// Lowercase form of a letter, the counterpart of [`to_uppercase`].
+pub(crate) fn to_lowercase(c: char) -> char {
+    #[cfg(feature = "unicode")]
+    {
+        let mut lower = c.to_lowercase();
+        match (lower.next(), lower.next()) {
+            (Some(lower), None) => lower,
+            _ => c,
+        }
+    }
+
+    #[cfg(not(feature = "unicode"))]
+    c.to_ascii_lowercase()
+}
+
+/// Parses letters of a set up to the closing `]`, the opening one being already consumed.
+fn parse_letter_set(
+    chars: &mut impl Iterator<Item = char>,
+    class: CharClass,
+) -> Result<Character, WordError> {
+    let mut set = String::from("[");
+    let mut letters: Vec<char> = Vec::new();
+
+    for c in chars.by_ref() {
+        set.push(c);
+        match c {
+            ']' if !letters.is_empty() => return Ok(Character::OneOf(letters)),
+            ']' => break,
+            c if class.contains(c) && !WILDCARDS.contains(&c) && c != '[' => {
+                let c = to_uppercase(c);
+                if !letters.contains(&c) {
+                    letters.push(c);
+                }
+            }
+            _ => return Err(WordError::InvalidCharValue(c)),
+        }
+    }
+
+    Err(WordError::InvalidLetterSet(set))
+}
+
+/// Splits an optional weight, e.g. how common the word is, off the end of a dictionary
+/// line like `"crane 4821"`. Lines without a weight get weight of 1.
+pub fn split_weight(line: &str) -> (&str, u32) {
+    match line.trim_end().rsplit_once(char::is_whitespace) {
+        Some((word, weight)) => match weight.parse() {
+            Ok(weight) => (word.trim_end(), weight),
+            Err(_) => (line, 1),
+        },
+        None => (line, 1),
+    }
+}
+
+/// Returns words from the dictionary embedded in the binary at compile time.
+pub fn default_words() -> impl Iterator<Item = &'static str> {
+    include_str!("words.txt").lines()
+}
+
+/// Reads words line by line from any buffered reader, e.g. stdin.
+pub fn words_from_reader<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<String>> {
+    reader.lines()
+}
+
+/// Collects lines read from a dictionary. Lines that are not valid UTF-8 are left out and
+/// counted, unless `strict` is set and they fail with their line number like any other
+/// read error does.
+pub fn decode_lines<I>(lines: I, strict: bool) -> Result<(Vec<String>, usize), Error>
+where
+    I: IntoIterator<Item = io::Result<String>>,
+{
+    let mut decoded = Vec::new();
+    let mut undecodable = 0;
+
+    for (i, line) in lines.into_iter().enumerate() {
+        match line {
+            Ok(line) => decoded.push(line),
+            Err(e) if !strict && e.kind() == io::ErrorKind::InvalidData => undecodable += 1,
+            Err(source) => {
+                return Err(Error::UnreadableLine {
+                    line: i + 1,
+                    source,
+                })
+            }
+        }
+    }
+
+    Ok((decoded, undecodable))
+}
+
+pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+where
+    P: AsRef<Path>,
+{
+    let file = File::open(filename)?;
+    Ok(io::BufReader::new(file).lines())
+}
+
+/// Same as [`read_lines`], but reads the file without blocking an async runtime, e.g.
+/// in a tokio-based service. Collect the lines before filtering them.
+#[cfg(feature = "tokio")]
+pub async fn read_lines_async<P>(
+    filename: P,
+) -> io::Result<impl tokio_stream::Stream<Item = io::Result<String>>>
+where
+    P: AsRef<Path>,
+{
+    use tokio::io::AsyncBufReadExt;
+
+    let file = tokio::fs::File::open(filename).await?;
+    let lines = tokio::io::BufReader::new(file).lines();
+    Ok(tokio_stream::wrappers::LinesStream::new(lines))
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison, clippy::useless_vec)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_parse_every_word_of_default_dictionary() {
+        assert!(default_words().count() > 0);
+        assert!(default_words().all(|w| Word::new(w).is_ok()));
+    }
+
+    #[test]
+    fn should_read_words_from_reader() {
+        let input: &[u8] = b"zorro\r\nzowie\n\naargh";
+        let actual: Vec<String> = words_from_reader(input).map(Result::unwrap).collect();
+        assert_eq!(actual, vec!["zorro", "zowie", "", "aargh"]);
+    }
+
+    #[test]
+    fn should_return_error_if_word_is_longer_than_5_chars() {
+        let actual: Result<Word, WordError> = "absd__".parse();
+        let expected = WordError::InvalidWordLength {
+            expected: 5,
+            actual: 6,
+        };
+        assert_eq!(actual.unwrap_err(), expected);
+    }
+
+    #[test]
+    fn should_return_error_if_word_is_shorter_than_5_chars() {
+        let actual: Result<Word, WordError> = "absd".parse();
+        let expected = WordError::InvalidWordLength {
+            expected: 5,
+            actual: 4,
+        };
+        assert_eq!(actual.unwrap_err(), expected);
+    }
+
+    #[test]
+    fn should_iterate_over_word_chars() {
+        let word = Word::new("z?*ie").unwrap();
+        let actual: Vec<&Character> = word.chars().collect();
+        assert_eq!(
+            actual,
+            vec![
+                &Character::Normal('Z'),
+                &Character::Wildcard,
+                &Character::Wildcard,
+                &Character::Normal('I'),
+                &Character::Normal('E')
+            ]
+        );
+        assert_eq!(word.len(), 5);
+        assert!(!word.is_empty());
+    }
+
+    #[test]
+    fn should_count_chars_instead_of_bytes_when_checking_length() {
+        let word = "cafés";
+        assert!(word.len() > 5);
+
+        let actual: Word = word.parse().unwrap();
+        assert_eq!(actual.0[3], Character::Normal(to_uppercase('é')));
+        assert_eq!(actual.len(), 5);
+    }
+
+    #[test]
+    fn should_parse_word_of_configured_length() {
+        let actual = Word::with_length("plants", 6).unwrap();
+        assert_eq!(actual.len(), 6);
+
+        let actual = Word::with_length("plant", 6).unwrap_err();
+        let expected = WordError::InvalidWordLength {
+            expected: 6,
+            actual: 5,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_reject_candidates_of_different_length_than_chosen_word() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let chosen_word = Word::with_length("pla***", 6).unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(!result.is_word_possible("plant", &excluded, &included));
+        assert!(result.is_word_possible("plants", &excluded, &included));
+        assert_eq!(result.possible_words.len(), 1);
+    }
+
+    #[test]
+    fn should_return_error_if_not_alpabetic_or_wildcard_char() {
+        let actual = Character::try_from('-').unwrap_err();
+        let expected = WordError::InvalidCharValue('-');
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_return_error_if_included_or_excluded_contains_not_alphabetic_char() {
+        let actual = "ab3".parse::<Included>().unwrap_err();
+        assert_eq!(actual, WordError::InvalidCharValue('3'));
+
+        let actual = "ab3".parse::<Excluded>().unwrap_err();
+        assert_eq!(actual, WordError::InvalidCharValue('3'));
+
+        let actual: Excluded = "aB".parse().unwrap();
+        assert_eq!(actual.letters, vec!['A', 'B']);
+    }
+
+    #[test]
+    fn should_parse_every_wildcard_char() {
+        for c in WILDCARDS {
+            assert!(Character::try_from(*c).unwrap().is_wildcard());
+        }
+        assert!(!Character::try_from('a').unwrap().is_wildcard());
+    }
+
+    #[test]
+    fn should_build_word_from_chars() {
+        let actual = Word::from_chars(&['c', 'r', '?', 'N', 'e']).unwrap();
+        assert_eq!(actual, Word::new("cr?ne").unwrap());
+
+        let actual = Word::from_chars(&['c', 'r']).unwrap_err();
+        let expected = WordError::InvalidWordLength {
+            expected: 5,
+            actual: 2,
+        };
+        assert_eq!(actual, expected);
+
+        let actual = Word::from_chars(&['c', 'r', '-', 'n', 'e']).unwrap_err();
+        assert_eq!(actual, WordError::InvalidCharValue('-'));
+    }
+
+    #[test]
+    fn should_parse_5_char_long_word() {
+        let word = "A?_*c";
+        let actual: Word = word.parse().unwrap();
+        assert_eq!(actual.0[0], Character::Normal('A'));
+        assert_eq!(actual.0[1], Character::Wildcard);
+        assert_eq!(actual.0[2], Character::Wildcard);
+        assert_eq!(actual.0[3], Character::Wildcard);
+        assert_eq!(actual.0[4], Character::Normal('C'));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_serialize_and_deserialize_word_and_constraints() {
+        let word = Word::new("z?*ie").unwrap();
+        let json = serde_json::to_string(&word).unwrap();
+        assert_eq!(json, "\"Z**IE\"");
+        assert_eq!(serde_json::from_str::<Word>(&json).unwrap(), word);
+
+        let excluded: Excluded = "wx".parse().unwrap();
+        let json = serde_json::to_string(&excluded).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Excluded>(&json).unwrap().letters,
+            excluded.letters
+        );
+
+        let included = Included::new(vec!['i']).and_at_not('r', Position::new(1, 5).unwrap());
+        let json = serde_json::to_string(&included).unwrap();
+        let actual: Included = serde_json::from_str(&json).unwrap();
+        assert_eq!(actual.letters, included.letters);
+        assert_eq!(actual.misplaced, included.misplaced);
+
+        assert!(serde_json::from_str::<Word>("\"zow1e\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_deserialize_word_of_any_length() {
+        for (word, length) in [("planet", 6), ("ba[st]e", 4), ("zow", 3)] {
+            let word = Word::with_length(word, length).unwrap();
+            let json = serde_json::to_string(&word).unwrap();
+            assert_eq!(serde_json::from_str::<Word>(&json).unwrap(), word);
+        }
+        assert_eq!(
+            serde_json::to_string(&Word::with_length("[st]", 1).unwrap()).unwrap(),
+            "\"[ST]\""
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_find_deserialized_positions_outside_of_word() {
+        let included: Included =
+            serde_json::from_str(r#"{"letters":["R"],"misplaced":[["R",200]]}"#).unwrap();
+        assert_eq!(
+            included.check_positions(5),
+            Err(WordError::InvalidPosition {
+                index: 200,
+                length: 5
+            })
+        );
+        assert!(included.check_positions(201).is_ok());
+
+        let excluded: Excluded =
+            serde_json::from_str(r#"{"letters":[],"max_counts":[],"positions":[["E",4]]}"#)
+                .unwrap();
+        assert!(excluded.check_positions(5).is_ok());
+        assert!(excluded.check_positions(4).is_err());
+    }
+
+    #[test]
+    fn should_display_word_as_uppercase_string() {
+        assert_eq!(Word::new("zowie").unwrap().to_string(), "ZOWIE");
+        assert_eq!(Word::new("z?*ie").unwrap().to_string(), "Z  IE");
+    }
+
+    #[test]
+    fn should_display_word_in_lowercase() {
+        let word = Word::new("Z?*iE").unwrap();
+        assert_eq!(word.to_lowercase_string(), "z  ie");
+        assert_eq!(word, Word::new("z??ie").unwrap());
+    }
+
+    #[test]
+    fn should_expose_matched_words_as_strings() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::new("z*r*o").unwrap());
+        result.is_word_possible("zorro", &excluded, &included);
+
+        let actual: Vec<String> = result.words().iter().map(Word::as_string).collect();
+        assert_eq!(actual, vec!["ZORRO"]);
+    }
+
+    #[test]
+    fn should_display_possible_words_one_per_line() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let chosen_word = Word::new("z*r*o").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+        result.is_word_possible("zorro", &excluded, &included);
+        result.is_word_possible("zerio", &excluded, &included);
+
+        assert_eq!(
+            result.to_string(),
+            "List of possible matching words:\n1. ZORRO\n2. ZERIO\nMatched 2 of 2 words\n"
+        );
+    }
+
+    #[test]
+    fn should_display_only_given_number_of_words() {
+        let excluded = Excluded::new(vec!['j']);
+        let included = Included::new(vec![]);
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+        for word in ["zorro", "zerio", "zowie", "jumpy", "zor"] {
+            result.is_word_possible(word, &excluded, &included);
+        }
+
+        assert_eq!(
+            format!("{:.1}", result),
+            "List of possible matching words:\n1. ZORRO\n... and 2 more\nMatched 3 of 4 words\n"
+        );
+        assert_eq!(format!("{:.3}", result), result.to_string());
+        assert_eq!(
+            format!("{:#.1}", result),
+            "List of possible matching words:\n1. zorro\n... and 2 more\nMatched 3 of 4 words\n"
+        );
+    }
+
+    #[test]
+    fn should_return_matching_word() {
+        let excluded = Excluded::new(vec!['w']);
+        let included = Included::new(vec![]);
+        let words = vec!["aahed", "aalii", "aargh", "zowie", "zorro"];
+        let chosen_word = Word::new("aargh").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert_eq!(
+            result.is_word_possible(words[0], &excluded, &included),
+            false
+        );
+        assert_eq!(
+            result.is_word_possible(words[1], &excluded, &included),
+            false
+        );
+        assert_eq!(
+            result.is_word_possible(words[2], &excluded, &included),
+            true
+        );
+        assert_eq!(
+            result.is_word_possible(words[3], &excluded, &included),
+            false
+        );
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("aargh").unwrap());
+    }
+
+    #[test]
+    fn should_return_none_if_word_contains_excluded_char() {
+        let excluded = Excluded::new(vec!['w']);
+        let included = Included::new(vec![]);
+        let words = vec!["zowie"];
+        let chosen_word = Word::new("aargh");
+        let mut result = WordsResult::new(chosen_word.unwrap());
+
+        assert_eq!(
+            result.is_word_possible(words[0], &excluded, &included),
+            false
+        );
+        assert_eq!(result.possible_words.len(), 0);
+    }
+
+    #[test]
+    fn should_return_only_word_without_excluded_char_at_wildcard_position() {
+        let excluded = Excluded::new(vec!['m']);
+        let included = Included::new(vec![]);
+        let words = ["zorro", "morro"];
+        let chosen_word = Word::new("*orro").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(result.is_word_possible(words[0], &excluded, &included));
+        assert!(!result.is_word_possible(words[1], &excluded, &included));
+        assert_eq!(
+            result.check(words[1], &excluded, &included),
+            Err(RejectReason::ExcludedLetter('M'))
+        );
+
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("zorro").unwrap());
+    }
+
+    #[test]
+    fn should_reject_word_containing_excluded_char_if_pattern_is_all_wildcards() {
+        let excluded = Excluded::new(vec!['w']);
+        let included = Included::new(vec![]);
+        let words = ["zowie", "zorro"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(!result.is_word_possible(words[0], &excluded, &included));
+        assert!(result.is_word_possible(words[1], &excluded, &included));
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("zorro").unwrap());
+    }
+
+    #[test]
+    fn should_return_word_if_it_matches_completly() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let words = vec!["zowie", "aaron"];
+        let chosen_word = Word::new("zowie").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert_eq!(
+            result.is_word_possible(words[0], &excluded, &included),
+            true
+        );
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("zowie").unwrap());
+    }
+
+    #[test]
+    fn should_return_word_if_it_matches_with_wildcards() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let words = vec!["zowie", "aaron"];
+        let chosen_word = Word::new("z?*ie").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert_eq!(
+            result.is_word_possible(words[0], &excluded, &included),
+            true
+        );
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("zowie").unwrap());
+    }
+
+    #[test]
+    fn should_return_matching_words_without_storing_them() {
+        let excluded = Excluded::new(vec!['w']);
+        let included = Included::new(vec![]);
+        let words = ["zowie", "zorro", "ab3de", "aargh", "morro"];
+        let chosen_word = Word::new("*orro").unwrap();
+        let result = WordsResult::new(chosen_word);
+
+        let actual: Vec<Word> = result
+            .matches(words.into_iter(), &excluded, &included)
+            .collect();
+        assert_eq!(
+            actual,
+            vec![Word::new("zorro").unwrap(), Word::new("morro").unwrap()]
+        );
+        assert_eq!(result.possible_words.len(), 0);
+    }
+
+    #[test]
+    fn should_sort_possible_words_alphabetically() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+        for word in ["zorro", "aargh", "Zerio"] {
+            result.is_word_possible(word, &excluded, &included);
+        }
+
+        result.sort();
+        assert_eq!(
+            result.possible_words,
+            vec![
+                Word::new("aargh").unwrap(),
+                Word::new("zerio").unwrap(),
+                Word::new("zorro").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn should_rank_words_with_most_common_letters_first() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let words = ["sassy", "sales", "tales", "males"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+        for word in words {
+            result.is_word_possible(word, &excluded, &included);
+        }
+
+        let ranked = result.ranked();
+        assert_eq!(ranked.len(), 4);
+        assert_eq!(*ranked[0].0, Word::new("males").unwrap());
+        assert_eq!(*ranked[1].0, Word::new("tales").unwrap());
+        assert_eq!(*ranked[3].0, Word::new("sassy").unwrap());
+        assert!(ranked[0].1 >= ranked[1].1);
+    }
+
+    #[test]
+    fn should_return_reason_why_word_is_rejected() {
+        let excluded = Excluded::new(vec!['w']).and_at_most('r', 2);
+        let included = Included::new(vec!['z']).and_at_not('o', Position::new(2, 5).unwrap());
+        let mut result = WordsResult::new(Word::new("*o***").unwrap());
+        result
+            .add_feedback(Feedback::new("aargh", "BBGBB").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            result.check("zorr", &excluded, &included),
+            Err(RejectReason::InvalidWord(WordError::InvalidWordLength {
+                expected: 5,
+                actual: 4
+            }))
+        );
+        assert_eq!(
+            result.check("zowie", &excluded, &included),
+            Err(RejectReason::ExcludedLetter('W'))
+        );
+        assert_eq!(
+            result.check("zorrr", &excluded, &included),
+            Err(RejectReason::TooManyOccurrences {
+                letter: 'R',
+                max: 2
+            })
+        );
+        assert_eq!(
+            result.check("zooms", &excluded, &included),
+            Err(RejectReason::MisplacedLetter {
+                letter: 'O',
+                index: 2
+            })
+        );
+        assert_eq!(
+            result.check("zeros", &excluded, &included),
+            Err(RejectReason::PositionMismatch {
+                index: 1,
+                expected: 'O',
+                found: 'E'
+            })
+        );
+        assert_eq!(
+            result.check("bohos", &excluded, &included),
+            Err(RejectReason::MissingIncluded('Z'))
+        );
+        assert_eq!(
+            result.check("zonal", &excluded, &included),
+            Err(RejectReason::FeedbackMismatch(0))
+        );
+        assert_eq!(result.check("zorro", &excluded, &included), Ok(()));
+    }
+
+    #[test]
+    fn should_skip_words_that_can_not_be_parsed() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let words = ["", "ab3de", "don't", "aahed"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(!result.is_word_possible(words[0], &excluded, &included));
+        assert!(!result.is_word_possible(words[1], &excluded, &included));
+        assert!(!result.is_word_possible(words[2], &excluded, &included));
+        assert!(result.is_word_possible(words[3], &excluded, &included));
+        assert_eq!(result.skipped(), 2);
+        assert_eq!(result.remaining(), 1);
+    }
+
+    #[test]
+    fn should_reject_word_with_included_char_if_it_does_not_match_pattern() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec!['i']);
+        let chosen_word = Word::new("****t").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(!result.is_word_possible("lions", &excluded, &included));
+        assert!(result.is_word_possible("light", &excluded, &included));
+        assert_eq!(result.possible_words.len(), 1);
+    }
+
+    #[test]
+    fn should_reject_word_with_included_char_if_it_contains_excluded_char() {
+        let excluded: Excluded = "h".parse().unwrap();
+        let included = Included::new(vec!['i']);
+        let chosen_word = Word::new("l**h*").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(!result.is_word_possible("light", &excluded, &included));
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn should_reject_words_with_more_occurrences_than_allowed() {
+        let excluded = Excluded::at_most('l', 1);
+        let included = Included::new(vec!['l']);
+        let words = ["lemon", "label", "tonic"];
+        let chosen_word = Word::new("l****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(result.is_word_possible(words[0], &excluded, &included));
+        assert!(!result.is_word_possible(words[1], &excluded, &included));
+        assert!(!result.is_word_possible(words[2], &excluded, &included));
+        assert_eq!(result.possible_words, vec![Word::new("lemon").unwrap()]);
+    }
+
+    #[test]
+    fn should_reject_words_with_included_char_at_banned_position() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::at_not('r', Position::new(1, 5).unwrap());
+        let words = ["horse", "bring", "salty"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(result.is_word_possible(words[0], &excluded, &included));
+        assert!(!result.is_word_possible(words[1], &excluded, &included));
+        assert!(!result.is_word_possible(words[2], &excluded, &included));
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("horse").unwrap());
+    }
+
+    #[test]
+    fn should_apply_green_yellow_and_grey_constraints_together() {
+        let excluded = Excluded::new(vec!['t', 'y']);
+        let included = Included::at_not('a', Position::new(2, 5).unwrap());
+        let words = ["slang", "stand", "shear", "spray", "blank"];
+        let chosen_word = Word::new("s****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        for word in words {
+            result.is_word_possible(word, &excluded, &included);
+        }
+        assert_eq!(result.possible_words, vec![Word::new("shear").unwrap()]);
+    }
+
+    #[test]
+    fn should_return_words_consistent_with_all_feedback_rounds() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec![]);
+        let words = ["horse", "rinse", "terse", "verse"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+        result
+            .add_feedback(Feedback::new("crane", "BYBBG").unwrap())
+            .unwrap();
+        result
+            .add_feedback(Feedback::new("verse", "BBGGG").unwrap())
+            .unwrap();
+
+        assert!(result.is_word_possible(words[0], &excluded, &included));
+        assert!(!result.is_word_possible(words[1], &excluded, &included));
+        assert!(!result.is_word_possible(words[2], &excluded, &included));
+        assert!(!result.is_word_possible(words[3], &excluded, &included));
+
+        let mut expected = WordsResult::new(Word::new("*****").unwrap());
+        expected.possible_words.push(Word::new("horse").unwrap());
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn should_check_included_chars_if_pattern_is_all_wildcards() {
+        let excluded = Excluded::default();
+        let included = Included::new(vec!['o', 'r']);
+        let words = ["zorro", "brown", "aahed"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(result.is_word_possible(words[0], &excluded, &included));
+        assert!(result.is_word_possible(words[1], &excluded, &included));
+        assert!(!result.is_word_possible(words[2], &excluded, &included));
+        assert_eq!(
+            result.possible_words,
+            vec![Word::new("zorro").unwrap(), Word::new("brown").unwrap()]
+        );
+    }
+
+    #[test]
+    fn should_return_only_words_containing_all_included_chars() {
+        let excluded = Excluded::default();
+        let included = Included::new(vec!['i', 't']);
+        let words = ["light", "lions", "focus"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert!(result.is_word_possible(words[0], &excluded, &included));
+        assert!(!result.is_word_possible(words[1], &excluded, &included));
+        assert!(!result.is_word_possible(words[2], &excluded, &included));
+        assert_eq!(result.possible_words, vec![Word::new("light").unwrap()]);
+        assert_eq!(
+            result.check(words[1], &excluded, &included),
+            Err(RejectReason::MissingIncluded('T'))
+        );
+    }
+
+    #[test]
+    fn should_return_words_containing_included_chars() {
+        let excluded = Excluded::new(vec![]);
+        let included = Included::new(vec!['i']);
+        let words = vec!["light", "focus"];
+        let chosen_word = Word::new("*****").unwrap();
+        let mut result = WordsResult::new(chosen_word);
+
+        assert_eq!(
+            result.is_word_possible(words[0], &excluded, &included),
+            true
+        );
+        assert_eq!(result.possible_words.len(), 1);
+        assert_eq!(result.possible_words[0], Word::new("light").unwrap());
+    }
+
+    #[test]
+    fn should_refine_possible_words_with_new_constraints() {
+        let mut result = WordsResult::new(Word::new("**rse").unwrap());
+        for word in ["horse", "terse", "verse", "nurse"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+
+        assert_eq!(
+            result.refine(&Excluded::new(vec!['n']), &Included::default()),
+            3
+        );
+        result
+            .add_feedback(Feedback::new("verse", "BGGGG").unwrap())
+            .unwrap();
+        assert_eq!(result.refine(&Excluded::default(), &Included::default()), 1);
+        assert_eq!(result.words(), &[Word::new("terse").unwrap()]);
+
+        result.reset();
+        assert!(result.is_empty());
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn should_match_accented_letters_regardless_of_case() {
+        let excluded = Excluded::new(vec!['â']);
+        let included = Included::new(vec!['é']);
+        let mut result = WordsResult::new(Word::new("*lève").unwrap());
+
+        assert!(result.is_word_possible("ÉLÈVE", &excluded, &included));
+        assert!(!result.is_word_possible("grâce", &excluded, &included));
+        assert_eq!(result.words()[0].to_lowercase_string(), "élève");
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn should_skip_words_written_with_combining_accents() {
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+        for word in ["forêt", "fore\u{0302}t", "océan"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+
+        assert_eq!(result.remaining(), 2);
+        assert_eq!(result.skipped(), 0);
+    }
+
+    #[test]
+    fn should_reject_excluded_letter_at_wildcard_position_in_any_scope() {
+        let included = Included::default();
+        let pattern = Word::new("****e").unwrap();
+        let excluded = Excluded::new(vec!['e', 'o']);
+        let mut result = WordsResult::new(Word::new("****e").unwrap());
+
+        assert!(!result.is_word_possible("horse", &excluded, &included));
+        assert!(!result.is_word_possible("terse", &excluded, &included));
+
+        let excluded = excluded.in_scope(ExclusionScope::Wildcards);
+        assert!(!result.is_word_possible("horse", &excluded, &included));
+        assert!(!result.is_word_possible("terse", &excluded, &included));
+        assert!(result.is_word_possible("lapse", &excluded, &included));
+        assert_eq!(
+            check(&pattern, &Word::new("terse").unwrap(), &excluded, &included),
+            Err(RejectReason::ExcludedLetter('E'))
+        );
+    }
+
+    #[test]
+    fn should_convert_word_and_io_errors_into_error() {
+        fn parse(word: &str) -> Result<Word, Error> {
+            Ok(Word::new(word)?)
+        }
+
+        let actual = parse("plants").unwrap_err();
+        assert!(matches!(
+            actual,
+            Error::Word(WordError::InvalidWordLength { .. })
+        ));
+        assert_eq!(
+            actual.to_string(),
+            WordError::InvalidWordLength {
+                expected: 5,
+                actual: 6
+            }
+            .to_string()
+        );
+
+        let actual = Error::from(io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert!(std::error::Error::source(&actual).is_none());
+        assert_eq!(actual.to_string(), "missing");
+    }
+
+    #[test]
+    fn should_merge_repeated_sets_without_duplicates() {
+        let excluded: Excluded = ["ab", "cA", "d"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert_eq!(excluded.letters, vec!['A', 'B', 'C', 'D']);
+
+        let included: Included = [
+            Included::new(vec!['e', 'E']),
+            Included::at_not('r', Position::new(1, 5).unwrap()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(included.letters, vec!['E']);
+        assert_eq!(
+            included.misplaced,
+            vec![('R', Position::new(1, 5).unwrap())]
+        );
+    }
+
+    #[test]
+    fn should_parse_letter_sets_as_single_characters() {
+        let actual: Word = "[st]o*e*".parse().unwrap();
+        assert_eq!(actual.0[0], Character::OneOf(vec!['S', 'T']));
+        assert_eq!(actual.len(), 5);
+        assert_eq!(format!("{:#}", actual), "[st]o e ");
+
+        assert_eq!(
+            Word::new("[]ore*").unwrap_err(),
+            WordError::InvalidLetterSet("[]".to_string())
+        );
+        assert_eq!(
+            Word::new("[st").unwrap_err(),
+            WordError::InvalidWordLength {
+                expected: 5,
+                actual: 1
+            }
+        );
+        assert_eq!(
+            Word::with_length("[st", 1).unwrap_err(),
+            WordError::InvalidLetterSet("[st".to_string())
+        );
+    }
+
+    #[test]
+    fn should_accept_only_words_with_letter_from_the_set() {
+        let mut result = WordsResult::new(Word::new("[st]o*e*").unwrap());
+        let excluded = Excluded::default();
+        let included = Included::default();
+
+        assert!(result.is_word_possible("tower", &excluded, &included));
+        assert!(result.is_word_possible("sober", &excluded, &included));
+        assert!(!result.is_word_possible("cower", &excluded, &included));
+        assert_eq!(
+            result.check("cower", &excluded, &included),
+            Err(RejectReason::NotOneOf {
+                index: 0,
+                expected: "ST".to_string(),
+                found: 'C'
+            })
+        );
+    }
+
+    #[test]
+    fn should_keep_single_entry_of_repeated_word() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::new("*orro").unwrap());
+
+        assert!(result.is_word_possible("zorro", &excluded, &included));
+        assert!(result.is_word_possible("ZORRO", &excluded, &included));
+        assert!(result.is_word_possible("morro", &excluded, &included));
+        assert_eq!(
+            result.words(),
+            &[Word::new("zorro").unwrap(), Word::new("morro").unwrap()]
+        );
+
+        result.reset();
+        assert!(result.is_word_possible("zorro", &excluded, &included));
+        assert_eq!(result.remaining(), 1);
+    }
+
+    #[test]
+    fn should_fork_search_from_cloned_result() {
+        let mut result = WordsResult::new(Word::new("**rse").unwrap());
+        for word in ["horse", "terse", "verse"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+
+        let mut fork = result.clone();
+        fork.refine(&Excluded::new(vec!['h']), &Included::default());
+
+        assert_eq!(result.remaining(), 3);
+        assert_eq!(fork.remaining(), 2);
+    }
+
+    #[test]
+    fn should_split_weight_off_dictionary_line() {
+        assert_eq!(split_weight("crane 4821"), ("crane", 4821));
+        assert_eq!(split_weight("crane\t12 "), ("crane", 12));
+        assert_eq!(split_weight("crane"), ("crane", 1));
+        assert_eq!(split_weight("cr ne"), ("cr ne", 1));
+    }
+
+    #[test]
+    fn should_rank_words_by_their_weight() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::new("*a***").unwrap());
+        for line in ["sales", "tales 1", "males 10", "sassy"] {
+            assert!(result.is_word_possible(line, &excluded, &included));
+        }
+
+        let males = Word::new("males").unwrap();
+        assert_eq!(result.weight(&males), 10);
+        assert_eq!(result.ranked()[0].0, &males);
+    }
+
+    #[test]
+    fn should_intersect_words_possible_on_both_boards() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let words = ["horse", "terse", "verse", "tease"];
+
+        let mut first = WordsResult::new(Word::new("**rse").unwrap());
+        let mut second = WordsResult::new(Word::new("t***e").unwrap());
+        for word in words {
+            first.is_word_possible(word, &excluded, &included);
+            second.is_word_possible(word, &excluded, &included);
+        }
+
+        assert_eq!(first.intersect(&second), vec![Word::new("terse").unwrap()]);
+        assert_eq!(second.intersect(&first), first.intersect(&second));
+    }
+
+    #[test]
+    fn should_match_candidate_without_side_effects() {
+        let pattern = Word::new("**rse").unwrap();
+        let excluded = Excluded::new(vec!['h']);
+        let included = Included::new(vec!['t']);
+
+        assert!(matches(
+            &pattern,
+            &Word::new("terse").unwrap(),
+            &excluded,
+            &included
+        ));
+        assert!(!matches(
+            &pattern,
+            &Word::new("verse").unwrap(),
+            &excluded,
+            &included
+        ));
+        assert!(!matches(
+            &pattern,
+            &Word::new("horse").unwrap(),
+            &excluded,
+            &Included::default()
+        ));
+    }
+
+    #[test]
+    fn should_accept_only_words_with_letter_count_in_range() {
+        let excluded = Excluded::default();
+        let included = Included::count('l', 2, 2);
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+
+        assert!(result.is_word_possible("llama", &excluded, &included));
+        assert!(result.is_word_possible("label", &excluded, &included));
+        assert!(!result.is_word_possible("lemon", &excluded, &included));
+        assert_eq!(
+            result.check("lolly", &excluded, &included),
+            Err(RejectReason::CountOutOfRange {
+                letter: 'L',
+                min: 2,
+                max: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn should_compare_results_by_chosen_word_and_possible_words() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut first = WordsResult::new(Word::new("**rse").unwrap());
+        let mut second = WordsResult::new(Word::new("**rse").unwrap());
+        for word in ["horse", "terse"] {
+            first.is_word_possible(word, &excluded, &included);
+        }
+        for word in ["terse", "horse"] {
+            second.is_word_possible(word, &excluded, &included);
+        }
+
+        assert_eq!(first, second);
+        second.refine(&Excluded::new(vec!['h']), &included);
+        assert_ne!(first, second);
+        assert_ne!(first, WordsResult::new(Word::new("*orse").unwrap()));
+    }
+
+    #[test]
+    fn should_exclude_letter_only_at_given_position() {
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+
+        let excluded = Excluded::at('e', Position::new(4, 5).unwrap());
+        assert!(!result.is_word_possible("horse", &excluded, &included));
+        assert!(result.is_word_possible("hoser", &excluded, &included));
+        assert!(result.is_word_possible("rocks", &excluded, &included));
+        assert_eq!(
+            result.check("horse", &excluded, &included),
+            Err(RejectReason::MisplacedLetter {
+                letter: 'E',
+                index: 4
+            })
+        );
+
+        let excluded = Excluded::new(vec!['e']);
+        assert!(!result.is_word_possible("hoser", &excluded, &included));
+    }
 
-#[derive(Debug)]
-pub struct Included(pub Vec<char>);
+    #[test]
+    fn should_count_undecodable_lines_unless_strict() {
+        let input: &[u8] = b"crane\n\xff\xfe\nhorse\n";
 
-impl FromStr for Included {
-    type Err = &'static str;
+        let (lines, undecodable) = decode_lines(words_from_reader(input), false).unwrap();
+        assert_eq!(lines, vec!["crane", "horse"]);
+        assert_eq!(undecodable, 1);
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Included(
-            s.chars().map(|c| char::to_ascii_uppercase(&c)).collect(),
-        ))
+        let actual = decode_lines(words_from_reader(input), true).unwrap_err();
+        assert!(matches!(actual, Error::UnreadableLine { line: 2, .. }));
     }
-}
-
-impl FromStr for Excluded {
-    type Err = &'static str;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Excluded(
-            s.chars().map(|c| char::to_ascii_uppercase(&c)).collect(),
-        ))
+    #[test]
+    fn should_count_known_letters_of_pattern() {
+        assert_eq!(Word::new("*****").unwrap().known_letters(), 0);
+        assert_eq!(Word::new("c[ar]*ne").unwrap().known_letters(), 3);
     }
-}
 
-#[derive(Debug, PartialEq)]
-pub struct Word(Vec<Character>);
+    #[test]
+    fn should_be_unconstrained_only_without_any_constraints() {
+        let result = WordsResult::new(Word::new("*****").unwrap());
+        assert!(result.is_unconstrained(&Excluded::default(), &Included::default()));
+        assert!(!result.is_unconstrained(&Excluded::at_most('l', 1), &Included::default()));
+        assert!(!result.is_unconstrained(&Excluded::default(), &Included::count('l', 2, 2)));
 
-impl Word {
-    fn new(word: &str) -> Result<Self, WordError> {
-        let output: Word = word.parse()?;
-        Ok(output)
+        let result = WordsResult::new(Word::new("[st]****").unwrap());
+        assert!(!result.is_unconstrained(&Excluded::default(), &Included::default()));
     }
-}
 
-#[derive(Debug)]
-pub struct WordsResult {
-    chosen_word: Word,
-    pub possible_words: Vec<Word>,
-}
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn should_parse_and_match_cyrillic_words() {
+        let word = "книга";
+        assert_eq!(word.len(), 10);
 
-impl<'a> WordsResult {
-    pub fn new(chosen_word: Word) -> Self {
-        Self {
-            chosen_word,
-            possible_words: Vec::new(),
-        }
+        let actual = Word::new(word).unwrap();
+        assert_eq!(actual.len(), 5);
+        assert_eq!(actual.to_string(), "КНИГА");
+
+        let excluded = Excluded::new(vec!['р']);
+        let included = Included::at_not('и', Position::new(0, 5).unwrap());
+        let mut result = WordsResult::new(Word::new("кн***").unwrap());
+        assert!(result.is_word_possible("КНИГА", &excluded, &included));
+        assert!(!result.is_word_possible("книжк", &excluded, &Included::new(vec!['а'])));
+        assert!(!result.is_word_possible("крица", &excluded, &included));
     }
 
-    pub fn is_word_possible(
-        &mut self,
-        target: &'a str,
-        excluded: &Excluded,
-        included: &Included,
-    ) -> bool {
-        let target_word: Word = target.parse().unwrap();
-        for (self_char, target_char) in self.chosen_word.0.iter().zip(target_word.0.iter()) {
-            let target_character = match target_char {
-                Character::Normal(c) => c,
-                _ => &' ',
-            };
+    #[test]
+    fn should_measure_how_constrained_search_is() {
+        let mut result = WordsResult::new(Word::new("**rse").unwrap());
+        assert_eq!(result.specificity(), 0.0);
 
-            if included.0.contains(target_character) {
-                break;
-            }
+        for word in ["horse", "terse", "verse", "crane", "plants", "zorro"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+        result.is_word_possible("nurse", &Excluded::new(vec!['n']), &Included::default());
 
-            let self_character = match self_char {
-                Character::Normal(c) => c,
-                Character::Wildcard => continue,
-            };
+        assert_eq!(result.specificity(), 0.5);
+        assert_eq!(result.remaining_bits(), 3f64.log2());
 
-            if excluded.0.contains(self_character) {
-                return false;
-            };
+        result.refine(&Excluded::new(vec!['h', 't']), &Included::default());
+        assert_eq!(result.remaining_bits(), 0.0);
+    }
 
-            if self_char != target_char {
-                return false;
-            };
-        }
+    #[test]
+    fn should_ignore_surrounding_whitespace() {
+        let expected = Word::new("crane").unwrap();
 
-        self.possible_words.push(target_word);
-        true
+        assert_eq!(Word::new("crane\r").unwrap(), expected);
+        assert_eq!(Word::new("crane\n").unwrap(), expected);
+        assert_eq!(Word::new("crane\r\n").unwrap(), expected);
+        assert_eq!("  crane ".parse::<Word>().unwrap(), expected);
+        assert!(Word::new("cr ne").is_err());
     }
-}
 
-impl Display for WordsResult {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "List of possible matching words:")?;
-        for (i, word) in self.possible_words.iter().enumerate() {
-            writeln!(f, "{}. {}\t", i + 1, word)?;
-        }
+    #[test]
+    fn should_filter_any_str_iterator_with_predicate() {
+        let pattern = Word::new("**rse").unwrap();
+        let excluded = Excluded::new(vec!['h']);
+        let included = Included::default();
+        let words = ["horse", "terse", "verse", "plants", "ab3de"];
 
-        Ok(())
+        let actual: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(constraint_predicate(&pattern, &excluded, &included))
+            .collect();
+        assert_eq!(actual, vec!["terse", "verse"]);
     }
-}
 
-impl FromStr for Word {
-    type Err = WordError;
+    #[test]
+    fn should_return_words_failing_fewest_constraints() {
+        let excluded = Excluded::new(vec!['h', 'v']);
+        let included = Included::new(vec!['t']);
+        let result = WordsResult::new(Word::new("**rse").unwrap());
+        let words = ["horse", "terse", "verse", "crane", "tease"];
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 5 {
-            return Err(WordError::InvalidWordLength(s.len()));
-        };
+        let actual = result.near_misses(words.iter().copied(), &excluded, &included, 1);
+        assert_eq!(
+            actual,
+            vec![(
+                Word::new("tease").unwrap(),
+                vec![RejectReason::PositionMismatch {
+                    index: 2,
+                    expected: 'R',
+                    found: 'A'
+                }]
+            ),]
+        );
 
-        let mut characters: Vec<Character> = Vec::new();
+        let actual = result.near_misses(words.iter().copied(), &excluded, &included, 2);
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual[0].0, Word::new("tease").unwrap());
+    }
 
-        for c in s.chars().into_iter() {
-            characters.push(Character::try_from(c)?);
-        }
+    #[test]
+    fn should_not_match_candidate_of_different_length() {
+        let pattern = Word::new("*****").unwrap();
+        let candidate = Word::with_length("lore", 4).unwrap();
+        let excluded = Excluded::default();
+        let included = Included::default();
 
-        Ok(Word(characters))
+        assert!(!matches(&pattern, &candidate, &excluded, &included));
+        assert_eq!(
+            check(&pattern, &candidate, &excluded, &included),
+            Err(RejectReason::InvalidWord(WordError::InvalidWordLength {
+                expected: 5,
+                actual: 4
+            }))
+        );
     }
-}
 
-#[derive(Debug, PartialEq)]
-enum Character {
-    Normal(char),
-    Wildcard,
-}
+    #[test]
+    fn should_return_error_if_position_is_out_of_word() {
+        assert_eq!(Position::new(4, 5).unwrap().index(), 4);
+        assert_eq!(
+            Position::new(5, 5),
+            Err(WordError::InvalidPosition {
+                index: 5,
+                length: 5
+            })
+        );
+        assert!(Position::new(300, 400).is_err());
+    }
 
-impl Display for Character {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Character::Normal(v) => v.to_ascii_uppercase(),
-                _ => ' ',
-            }
-        )
+    #[test]
+    fn should_set_bit_for_every_letter_of_word() {
+        let word = Word::new("a*b[cd]a").unwrap();
+        assert_eq!(word.letter_mask(), 0b11);
+        assert_eq!(
+            Word::new("zorro").unwrap().letter_mask(),
+            1 << 25 | 1 << 17 | 1 << 14
+        );
     }
-}
 
-impl Display for Word {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut word = String::with_capacity(5);
-        for c in self.0.iter() {
-            word.push(char::from_str(&c.to_string()).unwrap())
-        }
+    #[test]
+    fn should_reject_by_mask_only_words_that_surely_do_not_match() {
+        let excluded = Excluded::new(vec!['e']).letter_mask();
+        let included = Included::new(vec!['r']).and_count('o', 2, 2).letter_mask();
+
+        assert!(mask_allows(
+            Word::new("zorro").unwrap().letter_mask(),
+            excluded,
+            included
+        ));
+        assert!(mask_allows(
+            Word::new("robot").unwrap().letter_mask(),
+            excluded,
+            included
+        ));
+        assert!(!mask_allows(
+            Word::new("horse").unwrap().letter_mask(),
+            excluded,
+            included
+        ));
+        assert!(!mask_allows(
+            Word::new("jumbo").unwrap().letter_mask(),
+            excluded,
+            included
+        ));
 
-        write!(f, "{}", word)
+        let excluded = Excluded::new(vec!['e'])
+            .in_scope(ExclusionScope::Wildcards)
+            .letter_mask();
+        assert!(mask_allows(
+            Word::new("horse").unwrap().letter_mask(),
+            excluded,
+            included
+        ));
     }
-}
 
-impl TryFrom<char> for Character {
-    type Error = WordError;
+    #[test]
+    fn should_accept_only_letters_of_given_class() {
+        let actual = Word::with_class("crane", 5, CharClass::Ascii).unwrap();
+        assert_eq!(actual, Word::new("crane").unwrap());
+        assert_eq!(
+            Word::with_class("café", 4, CharClass::Ascii),
+            Err(WordError::InvalidCharValue('é'))
+        );
 
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        match value {
-            '*' | '_' | '?' => Ok(Self::Wildcard),
-            c if c.is_alphabetic() => Ok(Self::Normal(value.to_ascii_uppercase())),
-            _ => Err(WordError::InvalidCharValue(value)),
-        }
+        let hyphenated = CharClass::Custom(|c| c.is_alphabetic() || c == '-');
+        let actual = Word::with_class("t-shirt", 7, hyphenated).unwrap();
+        assert_eq!(actual.to_string(), "T-SHIRT");
+        assert!(Word::with_class("t-s*i[rt]t", 7, hyphenated).is_ok());
+        assert!(Word::with_length("t-shirt", 7).is_err());
     }
-}
-
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
 
-#[cfg(test)]
-mod tests {
+    #[test]
+    fn should_merge_letters_without_duplicates() {
+        let mut excluded = Excluded::new(vec!['a', 'b', 'A']);
+        excluded.merge(&Excluded::new(vec!['B', 'c']).and_at_most('d', 1));
+        assert_eq!(excluded.letters, vec!['A', 'B', 'C']);
+        assert_eq!(excluded.max_counts, vec![('D', 1)]);
 
-    use super::*;
+        let mut included = Included::new(vec!['r']);
+        included
+            .merge(&Included::new(vec!['R', 'e']))
+            .merge(&Included::new(vec!['e']));
+        assert_eq!(included.letters, vec!['R', 'E']);
+    }
 
     #[test]
-    fn should_return_error_if_word_is_longer_than_5_chars() {
-        let actual: Result<Word, WordError> = "absd__".parse();
-        let expected = WordError::InvalidWordLength(6);
-        assert_eq!(actual.unwrap_err(), expected);
+    fn should_match_only_pattern_ignoring_constraints() {
+        let mut result = WordsResult::new(Word::new("c?a?e").unwrap());
+        result
+            .add_feedback(Feedback::new("crane", "BBBBB").unwrap())
+            .unwrap();
+        let candidates = ["crane", "chase", "horse", "cra", "craze 12"];
+
+        let actual = result.pattern_matches(candidates.into_iter());
+        let expected: Vec<Word> = ["crane", "chase", "craze"]
+            .iter()
+            .map(|word| Word::new(word).unwrap())
+            .collect();
+        assert_eq!(actual, expected);
     }
 
     #[test]
-    fn should_return_error_if_word_is_shorter_than_5_chars() {
-        let actual: Result<Word, WordError> = "absd".parse();
-        let expected = WordError::InvalidWordLength(4);
-        assert_eq!(actual.unwrap_err(), expected);
+    fn should_order_words_alphabetically_with_wildcards_last() {
+        let mut words: Vec<Word> = ["zorro", "crane", "cr*ne", "Crate", "cr[ae]ne"]
+            .iter()
+            .map(|word| Word::new(word).unwrap())
+            .collect();
+        words.sort();
+
+        let actual: Vec<String> = words.iter().map(Word::to_string).collect();
+        assert_eq!(actual, vec!["CRANE", "CRATE", "CR NE", "CR[AE]NE", "ZORRO"]);
+
+        words.push(Word::new("CRANE").unwrap());
+        let unique: std::collections::BTreeSet<Word> = words.into_iter().collect();
+        assert_eq!(unique.len(), 5);
     }
 
     #[test]
-    fn should_return_error_if_not_alpabetic_or_wildcard_char() {
-        let actual = Character::try_from('-').unwrap_err();
-        let expected = WordError::InvalidCharValue('-');
-        assert_eq!(actual, expected);
+    fn should_filter_already_parsed_words() {
+        let dictionary = Dictionary::new(["zorro", "zerio", "horse", "zorro"], 5);
+        let mut result = WordsResult::new(Word::new("z****").unwrap());
+        let candidates = [dictionary.words(), &[Word::with_length("zoo", 3).unwrap()]].concat();
+
+        let actual =
+            result.filter_slice(&candidates, &Excluded::new(vec!['e']), &Included::default());
+        assert_eq!(actual, 1);
+        assert_eq!(result.possible_words, vec![Word::new("zorro").unwrap()]);
+        assert_eq!(result.checked(), 4);
     }
 
     #[test]
-    fn should_parse_5_char_long_word() {
-        let word = "A?_*c";
-        let actual: Word = word.parse().unwrap();
-        assert_eq!(actual.0[0], Character::Normal('A'));
-        assert_eq!(actual.0[1], Character::Wildcard);
-        assert_eq!(actual.0[2], Character::Wildcard);
-        assert_eq!(actual.0[3], Character::Wildcard);
-        assert_eq!(actual.0[4], Character::Normal('C'));
+    fn should_keep_weights_of_already_parsed_words() {
+        let dictionary = Dictionary::new(["zorro 7", "zowie", "horse 3"], 5);
+        let mut result = WordsResult::new(Word::new("z****").unwrap());
+        let weighted = dictionary.weighted_words();
+
+        let actual = result.filter_weighted(
+            weighted.iter().map(|(word, weight)| (word, *weight)),
+            &Excluded::default(),
+            &Included::default(),
+        );
+        assert_eq!(actual, 2);
+        assert_eq!(result.weight(&Word::new("zorro").unwrap()), 7);
+        assert_eq!(result.weight(&Word::new("zowie").unwrap()), 1);
     }
 
     #[test]
-    fn should_return_matching_word() {
-        let excluded = Excluded(vec!['w']);
-        let included = Included(vec![]);
-        let words = vec!["aahed", "aalii", "aargh", "zowie", "zorro"];
-        let chosen_word = Word::new("aargh").unwrap();
-        let mut result = WordsResult::new(chosen_word);
+    fn should_keep_only_words_containing_substring() {
+        let included = Included::contains("st");
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
 
+        assert!(result.is_word_possible("toast", &Excluded::default(), &included));
+        assert!(result.is_word_possible("store", &Excluded::default(), &included));
+        assert!(!result.is_word_possible("round", &Excluded::default(), &included));
         assert_eq!(
-            result.is_word_possible(words[0], &excluded, &included),
-            false
-        );
-        assert_eq!(
-            result.is_word_possible(words[1], &excluded, &included),
-            false
-        );
-        assert_eq!(
-            result.is_word_possible(words[2], &excluded, &included),
-            true
+            result.check("tsars", &Excluded::default(), &included),
+            Err(RejectReason::MissingSubstring("ST".to_string()))
         );
+    }
+
+    #[test]
+    fn should_order_equally_scored_words_by_tie_break() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+        for word in ["ffbgh", "abecd", "aabcd"] {
+            result.is_word_possible(word, &excluded, &included);
+        }
+
+        let words = |ranked: Vec<(&Word, f64)>| -> Vec<String> {
+            ranked.iter().map(|(word, _)| word.to_string()).collect()
+        };
+        assert_eq!(words(result.ranked()), vec!["AABCD", "ABECD", "FFBGH"]);
+
+        let options = RankOptions {
+            tie_break: TieBreak::DistinctLetters,
+            ..RankOptions::default()
+        };
         assert_eq!(
-            result.is_word_possible(words[3], &excluded, &included),
-            false
+            words(result.ranked_with(&options)),
+            vec!["ABECD", "AABCD", "FFBGH"]
         );
-        assert_eq!(result.possible_words.len(), 1);
-        assert_eq!(result.possible_words[0], Word::new("aargh").unwrap());
     }
 
     #[test]
-    fn should_return_none_if_word_contains_excluded_char() {
-        let excluded = Excluded(vec!['w']);
-        let included = Included(vec![]);
-        let words = vec!["zowie"];
-        let chosen_word = Word::new("aargh");
-        let mut result = WordsResult::new(chosen_word.unwrap());
+    fn should_rank_words_with_custom_strategy() {
+        struct Vowels;
+        impl GuessStrategy for Vowels {
+            fn score(&self, guess: &Word, _: &[&Word]) -> f64 {
+                let word = guess.to_string();
+                word.chars().filter(|c| "AEIOU".contains(*c)).count() as f64
+            }
+        }
 
-        assert_eq!(
-            result.is_word_possible(words[0], &excluded, &included),
-            false
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+        for word in ["crwth", "audio", "crane"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+
+        let ranked = result.ranked_by(&Vowels, &RankOptions::default());
+        assert_eq!(ranked[0], (&Word::new("audio").unwrap(), 4.0));
+        assert_eq!(ranked[2], (&Word::new("crwth").unwrap(), 0.0));
+    }
+
+    #[test]
+    fn should_rank_guesses_that_are_not_possible_words() {
+        let mut result = WordsResult::new(Word::new("cr***").unwrap());
+        for word in ["crwth", "audio", "crane"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+
+        let audio = Word::new("audio").unwrap();
+        let crane = Word::new("crane").unwrap();
+        let ranked = result.ranked_guesses_by(
+            &[&crane, &audio],
+            &FrequencyStrategy,
+            &RankOptions::default(),
         );
-        assert_eq!(result.possible_words.len(), 0);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].0, &crane);
+        assert_eq!(ranked[1], (&audio, 0.0));
+        assert!(!result.possible_words.contains(&audio));
     }
 
     #[test]
-    fn should_return_both_words_if_excluded_char_is_wildcard() {
-        let excluded = Excluded(vec!['m']);
-        let included = Included(vec![]);
-        let words = vec!["zorro", "morro"];
-        let chosen_word = Word::new("*orro").unwrap();
-        let mut result = WordsResult::new(chosen_word);
+    fn should_score_all_words_at_once_when_ranking() {
+        struct Counting(std::cell::Cell<usize>);
+        impl GuessStrategy for Counting {
+            fn score(&self, _: &Word, _: &[&Word]) -> f64 {
+                panic!("every word should be scored by score_all");
+            }
+
+            fn score_all(&self, guesses: &[&Word], _: &[&Word]) -> Vec<f64> {
+                self.0.set(self.0.get() + 1);
+                vec![1.0; guesses.len()]
+            }
+        }
 
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+        for word in ["crwth", "audio", "crane"] {
+            result.is_word_possible(word, &Excluded::default(), &Included::default());
+        }
+
+        let strategy = Counting(std::cell::Cell::new(0));
         assert_eq!(
-            result.is_word_possible(words[0], &excluded, &included),
-            true
+            result.ranked_by(&strategy, &RankOptions::default()).len(),
+            3
         );
+        assert_eq!(strategy.0.get(), 1);
+    }
+
+    #[test]
+    fn should_return_positions_of_wildcards() {
+        let result = WordsResult::new(Word::new("c?[ae]*e").unwrap());
+        assert_eq!(result.unknown_positions(), vec![1, 3]);
+
+        let result = WordsResult::new(Word::new("crane").unwrap());
+        assert!(result.unknown_positions().is_empty());
+    }
+
+    #[test]
+    fn should_rank_words_with_likely_letters_higher_without_filtering() {
+        let excluded = Excluded::default();
+        let included = Included::likely('s', 2.0).and_likely('t', 1.0);
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+        for word in ["males", "maler", "mater"] {
+            assert!(result.is_word_possible(word, &excluded, &included));
+        }
+
+        let options = RankOptions {
+            letter_weights: included.likely_letters().to_vec(),
+            ..RankOptions::default()
+        };
+        let ranked = result.ranked_with(&options);
+        let plain = result.ranked();
+        let score = |ranked: &[(&Word, f64)], word: &str| {
+            let word = Word::new(word).unwrap();
+            ranked.iter().find(|(w, _)| **w == word).unwrap().1
+        };
+        assert_eq!(score(&ranked, "males"), 2.0 * score(&plain, "males"));
+        assert_eq!(score(&ranked, "mater"), score(&plain, "mater"));
+    }
+
+    #[test]
+    fn should_explain_every_reason_word_is_rejected() {
+        let mut result = WordsResult::new(Word::new("h****").unwrap());
+        result
+            .add_feedback(Feedback::new("crane", "BBBBG").unwrap())
+            .unwrap();
+        let excluded = Excluded::new(vec!['l']);
+        let included = Included::default();
+
+        assert_eq!(result.explain("house", &excluded, &included), Ok(vec![]));
         assert_eq!(
-            result.is_word_possible(words[1], &excluded, &included),
-            true
+            result.explain("jello", &excluded, &included),
+            Ok(vec![
+                RejectReason::PositionMismatch {
+                    index: 0,
+                    expected: 'H',
+                    found: 'J'
+                },
+                RejectReason::ExcludedLetter('L'),
+                RejectReason::FeedbackMismatch(0),
+            ])
         );
+        assert!(result.explain("jell", &excluded, &included).is_err());
+    }
 
-        assert_eq!(result.possible_words.len(), 2);
-        assert_eq!(result.possible_words[0], Word::new("zorro").unwrap());
-        assert_eq!(result.possible_words[1], Word::new("morro").unwrap());
+    #[test]
+    fn should_list_every_constraint_with_whether_word_passes_it() {
+        let mut result = WordsResult::new(Word::new("h****").unwrap());
+        result
+            .add_feedback(Feedback::new("crane", "BBBBG").unwrap())
+            .unwrap();
+        let excluded = Excluded::new(vec!['l']).and_at_most('o', 1);
+        let included = Included::new(vec!['e']).and_at_not('s', Position::new(0, 5).unwrap());
+
+        let actual = result.checklist("jello", &excluded, &included).unwrap();
+        let expected = vec![
+            (
+                Requirement::Letter {
+                    index: 0,
+                    letter: 'H',
+                },
+                false,
+            ),
+            (Requirement::Present('E'), true),
+            (
+                Requirement::PresentNotAt {
+                    letter: 'S',
+                    index: 0,
+                },
+                false,
+            ),
+            (Requirement::Absent('L'), false),
+            (
+                Requirement::AtMost {
+                    letter: 'O',
+                    max: 1,
+                },
+                true,
+            ),
+            (Requirement::Feedback(0), false),
+        ];
+        assert_eq!(actual, expected);
+
+        let actual = result.checklist("house", &excluded, &included).unwrap();
+        assert!(actual.iter().all(|(_, passed)| *passed));
+        assert_eq!(actual[0].0.to_string(), "'H' at position 1");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn should_read_dictionary_lines_asynchronously() {
+        use tokio_stream::StreamExt;
+
+        let path = std::env::temp_dir().join("words_read_lines_async.txt");
+        std::fs::write(&path, "zorro\nzerio 12\n").unwrap();
+
+        let lines: Vec<String> = read_lines_async(&path)
+            .await
+            .unwrap()
+            .collect::<io::Result<_>>()
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let dictionary = Dictionary::new(lines, 5);
+        assert_eq!(dictionary.len(), 2);
+        assert!(read_lines_async("no/such/dictionary.txt").await.is_err());
     }
 
     #[test]
-    fn should_return_word_if_it_matches_completly() {
-        let excluded = Excluded(vec![]);
-        let included = Included(vec![]);
-        let words = vec!["zowie", "aaron"];
-        let chosen_word = Word::new("zowie").unwrap();
-        let mut result = WordsResult::new(chosen_word);
+    fn should_collect_letters_possible_at_each_position() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::with_length("c**", 3).unwrap());
+        for word in ["cat", "cot", "cab"] {
+            result.is_word_possible(word, &excluded, &included);
+        }
 
+        let actual = result.position_candidates();
+        assert_eq!(actual[0], BTreeSet::from(['C']));
+        assert_eq!(actual[1], BTreeSet::from(['A', 'O']));
         assert_eq!(
-            result.is_word_possible(words[0], &excluded, &included),
-            true
+            PositionGrid(&actual).to_string(),
+            "  1 2 3\nA . # .\nB . . #\nC # . .\nO . # .\nT . . #\n"
         );
-        assert_eq!(result.possible_words.len(), 1);
-        assert_eq!(result.possible_words[0], Word::new("zowie").unwrap());
     }
 
     #[test]
-    fn should_return_word_if_it_matches_with_wildcards() {
-        let excluded = Excluded(vec![]);
-        let included = Included(vec![]);
-        let words = vec!["zowie", "aaron"];
-        let chosen_word = Word::new("z?*ie").unwrap();
-        let mut result = WordsResult::new(chosen_word);
+    fn should_reject_known_letters_at_wildcards_only_when_asked() {
+        let included = Included::default();
+        let pattern = Word::new("a???a").unwrap();
+        let excluded = Excluded::default();
+        let mut result = WordsResult::new(pattern.clone());
+
+        assert!(result.is_word_possible("abaca", &excluded, &included));
+        assert!(result.is_word_possible("aroma", &excluded, &included));
 
+        let excluded = excluded.unused_at_wildcards(true);
+        let mut result = WordsResult::new(pattern.clone());
+        assert!(!result.is_word_possible("abaca", &excluded, &included));
+        assert!(result.is_word_possible("aroma", &excluded, &included));
         assert_eq!(
-            result.is_word_possible(words[0], &excluded, &included),
-            true
+            check(&pattern, &Word::new("abaca").unwrap(), &excluded, &included),
+            Err(RejectReason::RepeatedKnownLetter {
+                letter: 'A',
+                index: 2
+            })
         );
-        assert_eq!(result.possible_words.len(), 1);
-        assert_eq!(result.possible_words[0], Word::new("zowie").unwrap());
     }
 
     #[test]
-    fn should_return_words_containing_included_chars() {
-        let excluded = Excluded(vec![]);
-        let included = Included(vec!['i']);
-        let words = vec!["light", "focus"];
-        let chosen_word = Word::new("*****").unwrap();
-        let mut result = WordsResult::new(chosen_word);
+    fn should_match_pattern_ignoring_wildcards() {
+        let pattern = Word::with_length("c*[ao]t", 4).unwrap();
+        let coat = Word::with_length("coat", 4).unwrap();
+
+        assert_ne!(coat, pattern);
+        assert!(coat.matches_pattern(&pattern));
+        assert!(coat.matches_pattern(&coat));
+        assert!(!Word::with_length("chit", 4)
+            .unwrap()
+            .matches_pattern(&pattern));
+        assert!(!Word::with_length("cat", 3)
+            .unwrap()
+            .matches_pattern(&pattern));
+    }
 
+    #[test]
+    fn should_pick_best_guess_among_possible_words_or_whole_dictionary() {
+        let excluded = Excluded::default();
+        let included = Included::default();
+        let mut result = WordsResult::new(Word::new("**tch").unwrap());
+        assert_eq!(result.best_candidate_guess(), None);
+
+        for word in ["batch", "catch", "hatch", "latch", "match"] {
+            result.is_word_possible(word, &excluded, &included);
+        }
         assert_eq!(
-            result.is_word_possible(words[0], &excluded, &included),
-            true
+            result.best_candidate_guess(),
+            Some(&Word::new("batch").unwrap())
         );
-        assert_eq!(result.possible_words.len(), 1);
-        assert_eq!(result.possible_words[0], Word::new("light").unwrap());
+
+        let dictionary = Dictionary::new(["batch", "catch", "hatch", "latch", "match", "climb"], 5);
+        assert_eq!(
+            result.best_guess(&dictionary),
+            Some(&Word::new("climb").unwrap())
+        );
+    }
+
+    #[test]
+    fn should_reject_feedback_of_guess_with_different_length() {
+        let mut result = WordsResult::new(Word::new("*****").unwrap());
+        let actual = result
+            .add_feedback(Feedback::new("plants", "BBBBBB").unwrap())
+            .unwrap_err();
+
+        let expected = WordError::InvalidWordLength {
+            expected: 5,
+            actual: 6,
+        };
+        assert_eq!(actual, expected);
+        assert!(result.is_word_possible("zowie", &Excluded::default(), &Included::default()));
     }
 }
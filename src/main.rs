@@ -1,43 +1,774 @@
-use structopt::StructOpt;
-use words::{read_lines, Excluded, Included, Word, WordsResult};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, IsTerminal, Write},
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+};
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let opt = Opt::from_args();
-    println!("{:?}", opt);
+use rand::{rngs::StdRng, seq::IndexedRandom, SeedableRng};
+use structopt::{clap::ArgGroup, StructOpt};
+use words::{
+    colorize, decode_lines, default_words, feedback, read_feedback, read_lines, read_openers,
+    words_from_reader, write_openers, Constraints, Dictionary, EntropyStrategy, Error, Excluded,
+    ExclusionScope, Feedback, FrequencyStrategy, GuessStrategy, History, Included, Position,
+    PositionGrid, RankOptions, RejectReason, TieBreak, Word, WordError, WordsResult, WILDCARDS,
+};
 
-    let mut result = WordsResult::new(opt.word);
+fn main() {
+    if let Err(e) = run(Opt::from_args()) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn run(opt: Opt) -> Result<(), Error> {
+    if opt.practice {
+        return practice(&opt);
+    }
+
+    if opt.validate {
+        return validate(&opt);
+    }
+
+    if let Some(path) = &opt.compute_opener {
+        return compute_opener(&opt, path);
+    }
 
-    let excluded = match opt.excluded {
-        Some(e) => e,
-        None => Excluded(vec![]),
+    if opt.simulate {
+        return simulate(&opt);
+    }
+
+    let word = match &opt.word {
+        Some(word) => Word::with_length(word, opt.length)?,
+        None => Word::with_length(&"*".repeat(opt.length), opt.length)?,
+    };
+    let mut result = WordsResult::new(word);
+    let mut rounds = match &opt.replay {
+        Some(path) => read_feedback(BufReader::new(File::open(path)?), opt.length)?,
+        None => Vec::new(),
     };
+    rounds.extend(opt.feedback);
+
+    let mut constraints = Constraints::new(opt.length);
+    constraints.set_hard_mode(opt.hard);
+    for feedback in rounds.iter() {
+        constraints.add_feedback(feedback)?;
+    }
 
-    let included = match opt.included {
-        Some(i) => i,
-        None => Included(vec![]),
+    let mut excluded: Excluded = opt.excluded.into_iter().collect();
+    for (letter, position) in opt.exclude_positional {
+        excluded = excluded.and_at(letter, Position::new(position, opt.length)?);
+    }
+    if opt.exclude_at_wildcards_only {
+        excluded = excluded.in_scope(ExclusionScope::Wildcards);
+    }
+    excluded = excluded.unused_at_wildcards(opt.unused_at_wildcards);
+
+    let mut included: Included = opt.included.into_iter().collect();
+    for substring in opt.contains.iter() {
+        included = included.and_contains(substring);
+    }
+    for (letter, weight) in opt.likely {
+        included = included.and_likely(letter, weight);
+    }
+
+    if let Some(word) = &opt.explain {
+        for feedback in rounds {
+            result.add_feedback(feedback)?;
+        }
+        return explain(&result, word, opt.length, &excluded, &included);
+    }
+
+    let lines = dictionary_lines(opt.dictionary.as_deref(), opt.strict)?;
+    for line in lines.iter() {
+        result.is_word_possible(line.as_str(), &excluded, &included);
+    }
+    // Words only allowed as guesses are suggested too, but never counted as possible words.
+    let guesses = match &opt.guesses {
+        Some(path) => Some(Dictionary::with_roles(
+            lines.iter(),
+            dictionary_lines(Some(path), opt.strict)?,
+            opt.length,
+        )),
+        None => None,
     };
 
-    if let Ok(lines) = read_lines("src/words.txt") {
-        for line in lines.flatten() {
-            result.is_word_possible(line.as_str(), &excluded, &included);
+    // Feedback is applied one round at a time to record how many words every guess left.
+    let mut history = History::new();
+    for feedback in rounds {
+        result.add_feedback(feedback.clone())?;
+        history.push(feedback, result.refine(&excluded, &included));
+    }
+
+    if result.is_unconstrained(&excluded, &included) {
+        eprintln!("Word has no known letters and no other constraints are given, every word of the dictionary matches");
+    }
+
+    if result.skipped() > 0 {
+        eprintln!(
+            "Skipped {} dictionary lines that are not valid words",
+            result.skipped()
+        );
+    }
+
+    result.sort();
+    let top = opt.top.unwrap_or_else(|| result.remaining());
+    let mut out: Box<dyn Write> = match &opt.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match opt.format {
+        Format::Text => {
+            if !history.is_empty() {
+                match opt.lowercase {
+                    true => write!(out, "Guesses so far:\n{:#}", history)?,
+                    false => write!(out, "Guesses so far:\n{}", history)?,
+                }
+            }
+            let options = RankOptions {
+                tie_break: opt.tie_break,
+                letter_weights: included.likely_letters().to_vec(),
+            };
+            let strategy: &dyn GuessStrategy = match opt.strategy {
+                Strategy::Frequency => &FrequencyStrategy,
+                Strategy::Entropy => &EntropyStrategy,
+            };
+            // Openers computed in advance replace the suggestion while nothing is known.
+            let openers = match &opt.openers {
+                Some(path) if result.is_unconstrained(&excluded, &included) => {
+                    read_openers(BufReader::new(File::open(path)?), opt.length)?
+                }
+                _ => Vec::new(),
+            };
+            let mut ranked = match (openers.is_empty(), &guesses) {
+                (true, Some(guesses)) => {
+                    let words: Vec<&Word> = guesses.words().iter().collect();
+                    result.ranked_guesses_by(&words, strategy, &options)
+                }
+                (true, None) => result.ranked_by(strategy, &options),
+                (false, _) => Vec::new(),
+            };
+            ranked.retain(|(word, _)| constraints.allows_guess(word));
+            let suggestion = match openers.first() {
+                Some((word, score)) => Some((word, *score)),
+                None => ranked.first().copied(),
+            };
+            print_text(&mut out, &result, suggestion, top, opt.lowercase)?;
+            if opt.heatmap && !result.is_empty() {
+                let positions = result.position_candidates();
+                write!(
+                    out,
+                    "Letters possible at each position:\n{}",
+                    PositionGrid(&positions)
+                )?;
+            }
+            if result.is_empty() {
+                let candidates = lines.iter().map(String::as_str);
+                let near_misses = result.near_misses(candidates, &excluded, &included, 1);
+                let top = opt.top.unwrap_or(10);
+                print_near_misses(&mut out, &near_misses, top, opt.lowercase)?;
+            }
         }
+        Format::Line => print_line(&mut out, &result, top, opt.lowercase)?,
+        #[cfg(feature = "json")]
+        Format::Json => print_json(&mut out, &result, top, opt.lowercase)?,
+    }
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Lines of the dictionary given with `--dictionary`, or of the built-in word list.
+/// Lines that are not valid UTF-8 are left out with a warning, or fail in strict mode.
+fn dictionary_lines(path: Option<&Path>, strict: bool) -> Result<Vec<String>, Error> {
+    let lines: Box<dyn Iterator<Item = io::Result<String>>> = match path {
+        Some(path) if path == Path::new("-") => Box::new(words_from_reader(io::stdin().lock())),
+        Some(path) => match read_lines(path) {
+            Ok(lines) => Box::new(lines),
+            Err(e) => {
+                let message = format!("Can not open dictionary '{}': {}", path.display(), e);
+                return Err(Error::Io(io::Error::new(e.kind(), message)));
+            }
+        },
+        None => return Ok(default_words().map(String::from).collect()),
+    };
+
+    let (lines, undecodable) = decode_lines(lines, strict)?;
+    if undecodable > 0 {
+        eprintln!(
+            "Skipped {} dictionary lines that are not valid UTF-8, use --strict to fail on them",
+            undecodable
+        );
+    }
+    Ok(lines)
+}
+
+/// Dictionary of words from `path`, with words only allowed as guesses from `--guesses`.
+fn load_dictionary(opt: &Opt, path: Option<&Path>) -> Result<Dictionary, Error> {
+    let answers = dictionary_lines(path, opt.strict)?;
+    Ok(match &opt.guesses {
+        Some(guesses) => Dictionary::with_roles(
+            answers,
+            dictionary_lines(Some(guesses), opt.strict)?,
+            opt.length,
+        ),
+        None => Dictionary::new(answers, opt.length),
+    })
+}
+
+/// Prints every constraint `word` is checked against, marked whether it passes it.
+fn explain(
+    result: &WordsResult,
+    word: &str,
+    length: usize,
+    excluded: &Excluded,
+    included: &Included,
+) -> Result<(), Error> {
+    let checklist = result.checklist(word, excluded, included)?;
+    let word = Word::with_length(word, length)?;
+    if checklist.is_empty() {
+        println!("No constraints are given, {} matches", word);
+        return Ok(());
+    }
+
+    for (requirement, passed) in checklist.iter() {
+        match passed {
+            true => println!("pass {}", requirement),
+            false => println!("FAIL {}", requirement),
+        }
+    }
+
+    let failed = checklist.iter().filter(|(_, passed)| !passed).count();
+    match failed {
+        0 => println!("{} matches all {} constraints", word, checklist.len()),
+        _ => println!(
+            "{} fails {} of {} constraints",
+            word,
+            failed,
+            checklist.len()
+        ),
+    }
+    Ok(())
+}
+
+/// Prints every dictionary line that is not a valid word and returns an error if there
+/// was any.
+fn validate(opt: &Opt) -> Result<(), Error> {
+    let lines = dictionary_lines(opt.dictionary.as_deref(), true)?;
+    let invalid = Dictionary::validate(lines, opt.length);
+    if invalid.is_empty() {
+        println!("All dictionary lines are valid words");
+        return Ok(());
+    }
+
+    for (line, e) in invalid.iter() {
+        println!("Line {}: {}", line, e);
+    }
+    Err(Error::InvalidDictionary(invalid.len()))
+}
+
+/// Ranks every dictionary word as the first guess and writes the ranking to `path`,
+/// to be loaded with `--openers`.
+fn compute_opener(opt: &Opt, path: &Path) -> Result<(), Error> {
+    let dictionary = load_dictionary(opt, opt.dictionary.as_deref())?;
+    let openers = dictionary.rank_openers_with_progress(progress(opt, "Ranked"));
+    let (best, score) = match openers.first() {
+        Some(best) => *best,
+        None => return Err(Error::EmptyDictionary(opt.length)),
+    };
+
+    let mut file = BufWriter::new(File::create(path)?);
+    write_openers(&mut file, &openers)?;
+    file.flush()?;
+
+    println!(
+        "Best opener is {} ({:.2} bits), ranking of all {} words written to '{}'",
+        best,
+        score,
+        openers.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Solves a game against every answer of the dictionary and prints how many guesses
+/// it takes on average, starting with `--first-guess` when given.
+fn simulate(opt: &Opt) -> Result<(), Error> {
+    let dictionary = load_dictionary(opt, opt.dictionary.as_deref())?;
+    let answers = dictionary.answers().len();
+    if answers == 0 {
+        return Err(Error::EmptyDictionary(opt.length));
+    }
+
+    let first_guess = first_guess(opt)?;
+    let average = dictionary.average_guesses_with_progress(first_guess, progress(opt, "Solved"));
+    match first_guess {
+        Some(guess) => println!(
+            "Solved all {} words in {:.3} guesses on average, starting with {}",
+            answers, average, guess
+        ),
+        None => println!(
+            "Solved all {} words in {:.3} guesses on average",
+            answers, average
+        ),
+    }
+    Ok(())
+}
+
+/// Reports progress of a slow operation on stderr, unless `--quiet` is given.
+fn progress<'a>(opt: &'a Opt, action: &'a str) -> impl FnMut(usize, usize) + 'a {
+    move |done, total| {
+        if !opt.quiet && (done % 100 == 0 || done == total) {
+            eprint!("\r{} {} of {} words", action, done, total);
+            if done == total {
+                eprintln!();
+            }
+        }
+    }
+}
+
+/// `--first-guess`, checked to be as long as words that are solved.
+fn first_guess(opt: &Opt) -> Result<Option<&Word>, Error> {
+    match &opt.first_guess {
+        Some(guess) if guess.len() != opt.length => {
+            Err(Error::Word(WordError::InvalidWordLength {
+                expected: opt.length,
+                actual: guess.len(),
+            }))
+        }
+        guess => Ok(guess.as_ref()),
+    }
+}
+
+/// Picks a random answer and colors guesses read from stdin until it is found.
+/// An empty line or end of input gives up and reveals the answer.
+fn practice(opt: &Opt) -> Result<(), Error> {
+    let path = opt.dictionary.as_deref();
+    if path == Some(Path::new("-")) {
+        return Err(Error::StdinTakenByDictionary);
+    }
+
+    let dictionary = load_dictionary(opt, path)?;
+    let mut rng = match opt.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => rand::make_rng(),
     };
+    let answer = match dictionary.answers().choose(&mut rng) {
+        Some(answer) => *answer,
+        None => return Err(Error::EmptyDictionary(opt.length)),
+    };
+
+    let first_guess = first_guess(opt)?;
+
+    println!(
+        "Guess the {}-letter word, or send an empty line to give up",
+        opt.length
+    );
+    if let Some(guess) = &first_guess {
+        println!("First guess: {}", guess);
+    }
+
+    let color = !opt.no_color && io::stdout().is_terminal();
+    let mut candidates = dictionary.answers();
+    let mut history = History::new();
+    let first_line = first_guess.map(|guess| Ok(guess.to_string()));
+    for line in first_line.into_iter().chain(io::stdin().lock().lines()) {
+        let line = line?;
+        if line.trim().is_empty() {
+            break;
+        }
 
-    println!("{}", result);
+        let guess = match Word::with_length(line.trim(), opt.length) {
+            Ok(guess) if dictionary.role(&guess).is_some() => guess,
+            Ok(_) => {
+                eprintln!("'{}' is not in the word list", line.trim());
+                continue;
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                continue;
+            }
+        };
 
+        let tiles = feedback(&guess, answer);
+        match color {
+            true => println!("{}", colorize(&guess, &tiles)),
+            false => println!(
+                "{}",
+                tiles.iter().map(ToString::to_string).collect::<String>()
+            ),
+        }
+
+        let solved = guess == *answer;
+        let round = Feedback::from_tiles(guess, tiles)?;
+        candidates.retain(|candidate| round.is_satisfied_by(candidate));
+        history.push(round, candidates.len());
+
+        if solved {
+            println!("Solved in {} guesses:\n{}", history.len(), history);
+            println!("{}", history.share_grid());
+            return Ok(());
+        }
+    }
+
+    println!("The word was {}", answer);
+    print!("{}", history);
     Ok(())
 }
 
+fn print_text(
+    out: &mut impl Write,
+    result: &WordsResult,
+    suggestion: Option<(&Word, f64)>,
+    top: usize,
+    lowercase: bool,
+) -> io::Result<()> {
+    if result.is_empty() {
+        return writeln!(out, "No words match your constraints");
+    }
+
+    if let Some((word, score)) = suggestion {
+        match lowercase {
+            true => writeln!(out, "Suggested next guess: {:#} (score {:.2})", word, score)?,
+            false => writeln!(out, "Suggested next guess: {} (score {:.2})", word, score)?,
+        }
+    }
+
+    match lowercase {
+        true => write!(out, "{:#.*}", top, result),
+        false => write!(out, "{:.*}", top, result),
+    }
+}
+
+fn print_near_misses(
+    out: &mut impl Write,
+    near_misses: &[(Word, Vec<RejectReason>)],
+    top: usize,
+    lowercase: bool,
+) -> io::Result<()> {
+    if near_misses.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(
+        out,
+        "Words failing only one constraint, check the colors you gave:"
+    )?;
+    for (word, reasons) in near_misses.iter().take(top) {
+        match lowercase {
+            true => writeln!(out, "{:#}: {}", word, reasons[0])?,
+            false => writeln!(out, "{}: {}", word, reasons[0])?,
+        }
+    }
+    Ok(())
+}
+
+/// Prints matching words separated by spaces on a single line, an empty line when none
+/// match, e.g. for piping into other commands.
+fn print_line(
+    out: &mut impl Write,
+    result: &WordsResult,
+    top: usize,
+    lowercase: bool,
+) -> io::Result<()> {
+    let words: Vec<String> = result
+        .possible_words
+        .iter()
+        .take(top)
+        .map(|word| match lowercase {
+            true => word.to_lowercase_string(),
+            false => word.to_string(),
+        })
+        .collect();
+    writeln!(out, "{}", words.join(" "))
+}
+
+#[cfg(feature = "json")]
+fn print_json(
+    out: &mut impl Write,
+    result: &WordsResult,
+    top: usize,
+    lowercase: bool,
+) -> Result<(), Error> {
+    let words: Vec<String> = result
+        .possible_words
+        .iter()
+        .take(top)
+        .map(|word| match lowercase {
+            true => word.to_lowercase_string(),
+            false => word.to_string(),
+        })
+        .collect();
+
+    let output = serde_json::json!({ "count": result.remaining(), "words": words });
+    writeln!(out, "{}", serde_json::to_string(&output)?)?;
+    Ok(())
+}
+
+/// Parses a word of any length that can be played as a guess, so with no unknown letters.
+fn parse_guess(s: &str) -> Result<Word, String> {
+    match s.chars().find(|c| WILDCARDS.contains(c) || *c == '[') {
+        Some(c) => Err(format!("Guess can not contain '{}'", c)),
+        None => Word::with_length(s, s.trim().chars().count()).map_err(|e| e.to_string()),
+    }
+}
+
+/// Parses a char followed by its 1-based position, e.g. `e5`, into a 0-based position.
+fn parse_positional(s: &str) -> Result<(char, usize), String> {
+    let mut chars = s.chars();
+    let letter = match chars.next() {
+        Some(c) if c.is_alphabetic() => c,
+        _ => return Err(format!("Expected a char followed by position, got '{}'", s)),
+    };
+
+    match chars.as_str().parse::<usize>() {
+        Ok(position) if position > 0 => Ok((letter, position - 1)),
+        _ => Err(format!("Expected position counted from 1, got '{}'", s)),
+    }
+}
+
+/// Parses a char and its weight separated by a colon, e.g. `s:2.5`.
+fn parse_likely(s: &str) -> Result<(char, f64), String> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next(), chars.as_str().parse::<f64>()) {
+        (Some(letter), Some(':'), Ok(weight)) if letter.is_alphabetic() && weight >= 0.0 => {
+            Ok((letter, weight))
+        }
+        _ => Err(format!(
+            "Expected a char and its weight, e.g. 's:2.5', got '{}'",
+            s
+        )),
+    }
+}
+
+#[derive(Debug)]
+enum Format {
+    Text,
+    Line,
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "line" => Ok(Format::Line),
+            #[cfg(feature = "json")]
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Unknown output format '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Strategy {
+    Frequency,
+    Entropy,
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "frequency" => Ok(Strategy::Frequency),
+            "entropy" => Ok(Strategy::Entropy),
+            _ => Err(format!("Unknown strategy '{}'", s)),
+        }
+    }
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(
+    group = ArgGroup::with_name("simulation").multiple(true),
     name = "words",
     about = "Simple program that helps you find anwser to wordle's word of the day."
 )]
 struct Opt {
-    #[structopt(help = "5 character long word that you want to solve")]
-    word: Word,
-    #[structopt(short, long, help = "List of chars you want to omit")]
-    excluded: Option<Excluded>,
-    #[structopt(short, long, help = "List of chars you want to include")]
-    included: Option<Included>,
+    #[structopt(
+        required_unless_one = &["practice", "validate", "replay", "compute-opener", "simulate"],
+        help = "Word that you want to solve, use '*', '_' or '?' for unknown chars and e.g. '[st]' for one of a few letters"
+    )]
+    word: Option<String>,
+    #[structopt(
+        short,
+        long,
+        default_value = "5",
+        help = "Number of characters in the word you want to solve"
+    )]
+    length: usize,
+    #[structopt(
+        short,
+        long,
+        number_of_values = 1,
+        help = "List of chars you want to omit, can be given many times"
+    )]
+    excluded: Vec<Excluded>,
+    #[structopt(
+        long,
+        help = "Check excluded chars only at unknown positions, so they can still be known letters of the word"
+    )]
+    exclude_at_wildcards_only: bool,
+    #[structopt(
+        long,
+        help = "Don't fill unknown positions with letters the word already has at known positions"
+    )]
+    unused_at_wildcards: bool,
+    #[structopt(
+        long,
+        number_of_values = 1,
+        parse(try_from_str = parse_positional),
+        help = "Char that is not at given position, counted from 1, e.g. 'e5', can be given many times"
+    )]
+    exclude_positional: Vec<(char, usize)>,
+    #[structopt(
+        short,
+        long,
+        number_of_values = 1,
+        help = "List of chars you want to include, can be given many times"
+    )]
+    included: Vec<Included>,
+    #[structopt(
+        long,
+        number_of_values = 1,
+        help = "Letters that appear next to each other somewhere in the word, e.g. 'st', can be given many times"
+    )]
+    contains: Vec<String>,
+    #[structopt(
+        long,
+        number_of_values = 1,
+        parse(try_from_str = parse_likely),
+        help = "Char that is probably in the word with its weight, e.g. 's:2', ranks words with it higher without filtering out others"
+    )]
+    likely: Vec<(char, f64)>,
+    #[structopt(
+        short,
+        long,
+        help = "Guess and colors it got, e.g. 'crane:gybbg' (G - green, Y - yellow, B - grey)"
+    )]
+    feedback: Vec<Feedback>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Path to a file with one guess and its colors per line, e.g. 'crane gybbg', applied before any --feedback"
+    )]
+    replay: Option<PathBuf>,
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        help = "Path to a file with one word per line, optionally followed by its weight, e.g. 'crane 4821'. '-' reads from stdin. The built-in word list is used when omitted"
+    )]
+    dictionary: Option<PathBuf>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Path to a file with words allowed as guesses, but never the answer, for suggestions, --practice, --simulate and --compute-opener"
+    )]
+    guesses: Option<PathBuf>,
+    #[structopt(short, long, help = "Show only first N matching words")]
+    top: Option<usize>,
+    #[structopt(
+        long,
+        help = "Show which letters are still possible at each position of matching words"
+    )]
+    heatmap: bool,
+    #[structopt(
+        short,
+        long,
+        help = "Don't report progress of --compute-opener and --simulate on stderr"
+    )]
+    quiet: bool,
+    #[structopt(long, help = "Print words in lowercase")]
+    lowercase: bool,
+    #[structopt(
+        long,
+        default_value = "text",
+        help = "Output format, 'text', 'line' with all words on one line, or 'json' (requires the 'json' feature)"
+    )]
+    format: Format,
+    #[structopt(
+        long,
+        default_value = "alphabetical",
+        help = "Order of suggestions with equal scores, 'alphabetical', 'letters' (more distinct letters first) or 'weight'"
+    )]
+    tie_break: TieBreak,
+    #[structopt(
+        long,
+        default_value = "frequency",
+        help = "How the next guess is suggested, 'frequency' of letters or 'entropy' of feedback (slow for many words)"
+    )]
+    strategy: Strategy,
+    #[structopt(
+        long,
+        help = "Suggest only guesses that reuse every green and yellow letter of the feedback, as in hard mode"
+    )]
+    hard: bool,
+    #[structopt(
+        long,
+        group = "simulation",
+        help = "Play a game against a random word from the dictionary, reading guesses from stdin"
+    )]
+    practice: bool,
+    #[structopt(
+        long,
+        group = "simulation",
+        help = "Solve a game against every dictionary word and print the average number of guesses, slow for big dictionaries"
+    )]
+    simulate: bool,
+    #[structopt(
+        long,
+        help = "Seed for picking the practice word, to replay the same game"
+    )]
+    seed: Option<u64>,
+    #[structopt(
+        long,
+        help = "Check that every line of the dictionary is a valid word of given length"
+    )]
+    validate: bool,
+    #[structopt(
+        long,
+        help = "Check only given word against all constraints and print why it does not match"
+    )]
+    explain: Option<String>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Rank every dictionary word as the first guess and write the ranking to a file, slow for big dictionaries"
+    )]
+    compute_opener: Option<PathBuf>,
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "File written by --compute-opener, its best word is suggested while nothing is known"
+    )]
+    openers: Option<PathBuf>,
+    #[structopt(
+        long,
+        requires = "simulation",
+        parse(try_from_str = parse_guess),
+        help = "Word played as the first guess with --practice or --simulate, e.g. to try out an opener"
+    )]
+    first_guess: Option<Word>,
+    #[structopt(
+        long,
+        help = "Print practice feedback as plain letters, this is the default when not printing to a terminal"
+    )]
+    no_color: bool,
+    #[structopt(
+        long,
+        help = "Fail on dictionary lines that are not valid UTF-8 instead of skipping them"
+    )]
+    strict: bool,
+    #[structopt(
+        short,
+        long,
+        parse(from_os_str),
+        help = "Write matching words to a file instead of stdout, replacing its content"
+    )]
+    output: Option<PathBuf>,
 }
@@ -0,0 +1,129 @@
+use crate::{rank, Excluded, Included, Word, WordsResult};
+
+/// Several boards solved at once, e.g. in Quordle, where every guess is played on all of
+/// them. Each board has its own chosen word and constraints.
+#[derive(Debug, Clone, Default)]
+pub struct MultiBoard {
+    boards: Vec<(WordsResult, Excluded, Included)>,
+}
+
+impl MultiBoard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a board, candidates are checked against `excluded` and `included` together
+    /// with its chosen word and feedback.
+    pub fn add_board(
+        &mut self,
+        result: WordsResult,
+        excluded: Excluded,
+        included: Included,
+    ) -> &mut Self {
+        self.boards.push((result, excluded, included));
+        self
+    }
+
+    /// Checks `word` on every board, returns whether it is possible on any of them.
+    pub fn is_word_possible(&mut self, word: &str) -> bool {
+        let mut possible = false;
+        for (result, excluded, included) in self.boards.iter_mut() {
+            possible |= result.is_word_possible(word, excluded, included);
+        }
+        possible
+    }
+
+    /// Results of all boards, in the order they were added.
+    pub fn boards(&self) -> impl Iterator<Item = &WordsResult> {
+        self.boards.iter().map(|(result, _, _)| result)
+    }
+
+    pub fn len(&self) -> usize {
+        self.boards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    /// Word possible on any board that is expected to give the most information about all
+    /// boards together, i.e. with the highest sum of entropies over every board's
+    /// candidates. Boards with a single candidate left add nothing, so they are solved by
+    /// guessing it once no other guess is better.
+    pub fn best_shared_guess(&self) -> Option<&Word> {
+        let candidates: Vec<Vec<&Word>> = self
+            .boards()
+            .map(|result| result.possible_words.iter().collect())
+            .collect();
+
+        let mut best: Option<(&Word, f64)> = None;
+        for word in candidates.iter().flatten() {
+            let score: f64 = candidates
+                .iter()
+                .map(|candidates| rank::entropy(word, candidates))
+                .sum();
+
+            best = match best {
+                Some((_, best_score)) if score <= best_score => best,
+                _ => Some((word, score)),
+            };
+        }
+
+        best.map(|(word, _)| word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_keep_candidates_of_every_board() {
+        let mut boards = MultiBoard::new();
+        boards
+            .add_board(
+                WordsResult::new(Word::new("****e").unwrap()),
+                Excluded::default(),
+                Included::default(),
+            )
+            .add_board(
+                WordsResult::new(Word::new("*****").unwrap()),
+                Excluded::new(vec!['e']),
+                Included::default(),
+            );
+
+        for word in ["horse", "crane", "zorro", "jumpy"] {
+            boards.is_word_possible(word);
+        }
+
+        let actual: Vec<usize> = boards.boards().map(WordsResult::remaining).collect();
+        assert_eq!(actual, vec![2, 2]);
+    }
+
+    #[test]
+    fn should_pick_guess_splitting_all_boards() {
+        let mut boards = MultiBoard::new();
+        boards
+            .add_board(
+                WordsResult::new(Word::new("***se").unwrap()),
+                Excluded::default(),
+                Included::default(),
+            )
+            .add_board(
+                WordsResult::new(Word::new("**nic").unwrap()),
+                Excluded::default(),
+                Included::default(),
+            );
+
+        for word in ["verse", "horse", "terse", "sonic", "tonic", "ionic"] {
+            boards.is_word_possible(word);
+        }
+
+        assert_eq!(
+            boards.best_shared_guess(),
+            Some(&Word::new("terse").unwrap())
+        );
+        assert_eq!(MultiBoard::new().best_shared_guess(), None);
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::{BufRead, Write};
+
+use crate::{Error, Word};
+
+/// Writes openers ranked by [`crate::Dictionary::rank_openers`], one word and its score
+/// per line, e.g. `CRANE 5.7435`, so they don't have to be computed again.
+pub fn write_openers<W: Write>(mut writer: W, openers: &[(&Word, f64)]) -> Result<(), Error> {
+    for (word, score) in openers {
+        writeln!(writer, "{} {:.4}", word, score)?;
+    }
+    Ok(())
+}
+
+/// Reads openers written by [`write_openers`], in the order they were written. Empty
+/// lines are left out.
+pub fn read_openers<R: BufRead>(reader: R, length: usize) -> Result<Vec<(Word, f64)>, Error> {
+    let mut openers = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let invalid = || Error::InvalidOpenerLine {
+            line: i + 1,
+            content: line.clone(),
+        };
+        let (word, score) = line.trim().split_once(' ').ok_or_else(invalid)?;
+        let word = Word::with_length(word, length).map_err(|_| invalid())?;
+        let score = score.trim().parse().map_err(|_| invalid())?;
+        openers.push((word, score));
+    }
+
+    Ok(openers)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_read_written_openers() {
+        let crane = Word::new("crane").unwrap();
+        let zorro = Word::new("zorro").unwrap();
+        let mut cache = Vec::new();
+        write_openers(&mut cache, &[(&crane, 5.74351), (&zorro, 1.0)]).unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&cache),
+            "CRANE 5.7435\nZORRO 1.0000\n"
+        );
+
+        let actual = read_openers(cache.as_slice(), 5).unwrap();
+        assert_eq!(actual, vec![(crane, 5.7435), (zorro, 1.0)]);
+
+        let actual = read_openers("CRANE 5.7\nCRANE\n".as_bytes(), 5).unwrap_err();
+        assert!(matches!(actual, Error::InvalidOpenerLine { line: 2, .. }));
+    }
+}
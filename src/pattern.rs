@@ -0,0 +1,82 @@
+use crate::{Character, Position, Word, WordError, DEFAULT_WORD_LENGTH};
+
+/// Builds a pattern one known (green) letter at a time, positions left unset are wildcards.
+#[derive(Debug, Clone)]
+pub struct PatternBuilder {
+    characters: Vec<Character>,
+}
+
+impl PatternBuilder {
+    /// Creates a pattern of given length with only wildcards.
+    pub fn new(length: usize) -> Self {
+        Self {
+            characters: vec![Character::Wildcard; length],
+        }
+    }
+
+    /// Sets the letter at `position`, replacing whatever was set there before.
+    pub fn set(&mut self, position: Position, letter: char) -> Result<&mut Self, WordError> {
+        let length = self.characters.len();
+        let character = match self.characters.get_mut(position.index()) {
+            Some(character) => character,
+            None => {
+                return Err(WordError::InvalidPosition {
+                    index: position.index(),
+                    length,
+                })
+            }
+        };
+
+        *character = match Character::try_from(letter)? {
+            Character::Normal(c) => Character::Normal(c),
+            _ => return Err(WordError::InvalidCharValue(letter)),
+        };
+        Ok(self)
+    }
+
+    pub fn build(&self) -> Word {
+        Word(self.characters.clone())
+    }
+}
+
+impl Default for PatternBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_WORD_LENGTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_build_pattern_from_letters_set_one_by_one() {
+        let mut builder = PatternBuilder::default();
+        builder
+            .set(Position::new(0, 5).unwrap(), 'c')
+            .unwrap()
+            .set(Position::new(4, 5).unwrap(), 'e')
+            .unwrap();
+        assert_eq!(builder.build(), Word::new("c***e").unwrap());
+
+        builder.set(Position::new(4, 5).unwrap(), 'y').unwrap();
+        assert_eq!(builder.build(), Word::new("c***y").unwrap());
+    }
+
+    #[test]
+    fn should_return_error_if_letter_can_not_be_set() {
+        let mut builder = PatternBuilder::new(4);
+        let actual = builder.set(Position::new(4, 5).unwrap(), 'c').unwrap_err();
+        assert_eq!(
+            actual,
+            WordError::InvalidPosition {
+                index: 4,
+                length: 4
+            }
+        );
+
+        let actual = builder.set(Position::new(0, 4).unwrap(), '*').unwrap_err();
+        assert_eq!(actual, WordError::InvalidCharValue('*'));
+    }
+}
@@ -0,0 +1,296 @@
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+
+use crate::{feedback, Character, Tile, Word};
+
+/// How ranked words are ordered, see [`crate::WordsResult::ranked_with`].
+#[derive(Debug, Clone, Default)]
+pub struct RankOptions {
+    pub tie_break: TieBreak,
+    /// Scores of words containing any of these letters are multiplied by their weights,
+    /// e.g. with [`crate::Included::likely_letters`].
+    pub letter_weights: Vec<(char, f64)>,
+}
+
+impl RankOptions {
+    /// Product of weights of all weighted letters in `word`, 1.0 if it has none.
+    pub(crate) fn letter_bonus(&self, word: &Word) -> f64 {
+        self.letter_weights
+            .iter()
+            .filter(|(letter, _)| word.0.contains(&Character::Normal(*letter)))
+            .map(|(_, weight)| weight)
+            .product()
+    }
+}
+
+/// Order of words with equal scores.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TieBreak {
+    /// Alphabetical order.
+    #[default]
+    Alphabetical,
+    /// Words with more distinct letters first, as they tell more about the answer.
+    DistinctLetters,
+    /// Words with higher weight first, e.g. more common words.
+    Weight,
+}
+
+impl TieBreak {
+    /// Compares equally scored words `a` and `b`, words equal by this rule are ordered
+    /// alphabetically, so the order is always the same.
+    pub(crate) fn compare(self, a: (&Word, u32), b: (&Word, u32)) -> Ordering {
+        let order = match self {
+            Self::Alphabetical => Ordering::Equal,
+            Self::DistinctLetters => distinct_letters(b.0).cmp(&distinct_letters(a.0)),
+            Self::Weight => b.1.cmp(&a.1),
+        };
+        order.then_with(|| a.0.cmp(b.0))
+    }
+}
+
+impl FromStr for TieBreak {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "alphabetical" => Ok(Self::Alphabetical),
+            "letters" => Ok(Self::DistinctLetters),
+            "weight" => Ok(Self::Weight),
+            _ => Err(format!("Unknown tie-break '{}'", s)),
+        }
+    }
+}
+
+/// Scores how good `guess` is as the next guess when the answer is one of `candidates`,
+/// higher is better. Implement it to rank words with your own heuristic, see
+/// [`crate::WordsResult::ranked_by`].
+pub trait GuessStrategy {
+    fn score(&self, guess: &Word, candidates: &[&Word]) -> f64;
+
+    /// Scores every one of `guesses` against the same `candidates`, in order. Override it
+    /// when something computed from the candidates can be shared between guesses.
+    fn score_all(&self, guesses: &[&Word], candidates: &[&Word]) -> Vec<f64> {
+        guesses
+            .iter()
+            .map(|guess| self.score(guess, candidates))
+            .collect()
+    }
+}
+
+/// Scores words by how common their letters are at each position among candidates.
+/// Fast, so it is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrequencyStrategy;
+
+impl GuessStrategy for FrequencyStrategy {
+    fn score(&self, guess: &Word, candidates: &[&Word]) -> f64 {
+        let frequencies = positional_frequencies(candidates.iter().copied());
+        frequency_score(guess, &frequencies, candidates.len())
+    }
+
+    /// Counts letters of the candidates once for all guesses.
+    fn score_all(&self, guesses: &[&Word], candidates: &[&Word]) -> Vec<f64> {
+        let frequencies = positional_frequencies(candidates.iter().copied());
+        guesses
+            .iter()
+            .map(|guess| frequency_score(guess, &frequencies, candidates.len()))
+            .collect()
+    }
+}
+
+/// Scores words by the expected information their feedback gives, in bits. Better
+/// guesses than [`FrequencyStrategy`], but checks every guess against every candidate.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntropyStrategy;
+
+impl GuessStrategy for EntropyStrategy {
+    fn score(&self, guess: &Word, candidates: &[&Word]) -> f64 {
+        entropy(guess, candidates)
+    }
+}
+
+fn distinct_letters(word: &Word) -> usize {
+    let mut seen: Vec<&char> = Vec::with_capacity(word.len());
+    for c in word.0.iter() {
+        if let Character::Normal(c) = c {
+            if !seen.contains(&c) {
+                seen.push(c);
+            }
+        }
+    }
+    seen.len()
+}
+
+/// For every position, how many of `words` have given letter there.
+pub(crate) fn positional_frequencies<'a>(
+    words: impl Iterator<Item = &'a Word>,
+) -> Vec<HashMap<char, u32>> {
+    let mut frequencies: Vec<HashMap<char, u32>> = Vec::new();
+
+    for word in words {
+        if frequencies.len() < word.len() {
+            frequencies.resize_with(word.len(), HashMap::new);
+        }
+
+        for (i, c) in word.0.iter().enumerate() {
+            if let Character::Normal(c) = c {
+                *frequencies[i].entry(*c).or_insert(0) += 1;
+            }
+        }
+    }
+
+    frequencies
+}
+
+/// Sums positional frequencies of the word's letters, relative to `total` words.
+/// Every letter is counted only once, so words with repeated letters score lower.
+pub(crate) fn frequency_score(
+    word: &Word,
+    frequencies: &[HashMap<char, u32>],
+    total: usize,
+) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut seen: Vec<char> = Vec::with_capacity(word.len());
+    let mut score = 0;
+
+    for (i, c) in word.0.iter().enumerate() {
+        if let Character::Normal(c) = c {
+            if seen.contains(c) {
+                continue;
+            }
+            seen.push(*c);
+
+            score += frequencies
+                .get(i)
+                .and_then(|f| f.get(c))
+                .copied()
+                .unwrap_or(0);
+        }
+    }
+
+    score as f64 / total as f64
+}
+
+/// Expected information, in bits, that playing `guess` gives about which of the
+/// `candidates` is the answer. Candidates are grouped by the feedback `guess` would get
+/// against each of them, and Shannon entropy is computed over group sizes.
+pub(crate) fn entropy(guess: &Word, candidates: &[&Word]) -> f64 {
+    let mut groups: HashMap<Vec<Tile>, usize> = HashMap::new();
+    for candidate in candidates {
+        *groups.entry(feedback(guess, candidate)).or_insert(0) += 1;
+    }
+
+    let total = candidates.len() as f64;
+    groups
+        .values()
+        .map(|size| {
+            let p = *size as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_count_letters_at_each_position() {
+        let words = [Word::new("crane").unwrap(), Word::new("crate").unwrap()];
+        let actual = positional_frequencies(words.iter());
+
+        assert_eq!(actual.len(), 5);
+        assert_eq!(actual[0][&'C'], 2);
+        assert_eq!(actual[3][&'N'], 1);
+        assert_eq!(actual[3][&'T'], 1);
+    }
+
+    #[test]
+    fn should_compute_entropy_of_feedback_groups() {
+        let words = [
+            Word::new("crane").unwrap(),
+            Word::new("crate").unwrap(),
+            Word::new("zorro").unwrap(),
+        ];
+        let candidates: Vec<&Word> = words.iter().collect();
+
+        assert_eq!(entropy(&words[2], &candidates[..2]), 0.0);
+        assert_eq!(entropy(&words[0], &candidates[..2]), 1.0);
+        assert!(entropy(&words[0], &candidates) > 1.5);
+    }
+
+    #[test]
+    fn should_compute_entropy_of_long_words() {
+        let word = |s: String| Word::with_length(&s, s.len()).unwrap();
+        let words = [
+            word("a".repeat(50)),
+            word(format!("{}b", "a".repeat(49))),
+            word(format!("b{}", "a".repeat(49))),
+        ];
+        let candidates: Vec<&Word> = words.iter().collect();
+
+        assert_eq!(entropy(&words[0], &candidates[1..]), 1.0);
+    }
+
+    #[test]
+    fn should_not_count_repeated_letters_twice() {
+        let words = [Word::new("eerie").unwrap(), Word::new("eager").unwrap()];
+        let frequencies = positional_frequencies(words.iter());
+
+        assert_eq!(frequency_score(&words[0], &frequencies, 2), 2.0);
+        assert_eq!(frequency_score(&words[1], &frequencies, 2), 2.5);
+    }
+
+    #[test]
+    fn should_break_ties_by_chosen_rule() {
+        let eerie = Word::new("eerie").unwrap();
+        let crane = Word::new("crane").unwrap();
+
+        let actual = TieBreak::Alphabetical.compare((&eerie, 1), (&crane, 1));
+        assert_eq!(actual, Ordering::Greater);
+        let actual = TieBreak::DistinctLetters.compare((&crane, 1), (&eerie, 1));
+        assert_eq!(actual, Ordering::Less);
+        let actual = TieBreak::Weight.compare((&crane, 1), (&eerie, 5));
+        assert_eq!(actual, Ordering::Greater);
+        let actual = TieBreak::Weight.compare((&crane, 5), (&eerie, 5));
+        assert_eq!(actual, Ordering::Less);
+    }
+
+    #[test]
+    fn should_score_guesses_with_chosen_strategy() {
+        let words = [
+            Word::new("crane").unwrap(),
+            Word::new("crate").unwrap(),
+            Word::new("zorro").unwrap(),
+        ];
+        let candidates: Vec<&Word> = words.iter().collect();
+
+        assert_eq!(EntropyStrategy.score(&words[2], &candidates[..2]), 0.0);
+        let frequencies = positional_frequencies(words.iter());
+        assert_eq!(
+            FrequencyStrategy.score(&words[0], &candidates),
+            frequency_score(&words[0], &frequencies, 3)
+        );
+    }
+
+    #[test]
+    fn should_score_all_guesses_like_one_by_one() {
+        let words = [
+            Word::new("crane").unwrap(),
+            Word::new("crate").unwrap(),
+            Word::new("zorro").unwrap(),
+        ];
+        let candidates: Vec<&Word> = words.iter().collect();
+
+        for strategy in [&FrequencyStrategy as &dyn GuessStrategy, &EntropyStrategy] {
+            let expected: Vec<f64> = candidates
+                .iter()
+                .map(|guess| strategy.score(guess, &candidates))
+                .collect();
+            assert_eq!(strategy.score_all(&candidates, &candidates), expected);
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Display;
+
+use crate::{Character, Excluded, Included, RejectReason, Word};
+
+/// A single constraint a word is checked against, see [`crate::WordsResult::checklist`].
+/// Indices are counted from 0, but displayed counted from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Requirement {
+    /// Known letter of the chosen word.
+    Letter { index: usize, letter: char },
+    /// One of a few letters of the chosen word, e.g. `[st]`.
+    OneOf { index: usize, letters: Vec<char> },
+    /// Excluded letter.
+    Absent(char),
+    /// Letter that can not appear more than `max` times.
+    AtMost { letter: char, max: usize },
+    /// Letter that is not at `index`, but may still be anywhere else.
+    NotAt { letter: char, index: usize },
+    /// Wildcards are not filled with known letters, see [`Excluded::unused_at_wildcards`].
+    UnusedAtWildcards,
+    /// Included letter.
+    Present(char),
+    /// Included letter that is not at `index`.
+    PresentNotAt { letter: char, index: usize },
+    /// Letter appearing from `min` to `max` times.
+    Count {
+        letter: char,
+        min: usize,
+        max: usize,
+    },
+    /// Part of the word, e.g. `"st"`.
+    Contains(String),
+    /// Feedback of a guess, counted from 0.
+    Feedback(usize),
+}
+
+impl Requirement {
+    /// Checks whether `reason` is a failure of this requirement.
+    pub(crate) fn is_failed_by(&self, reason: &RejectReason) -> bool {
+        match (self, reason) {
+            (Self::Letter { index, .. }, RejectReason::PositionMismatch { index: i, .. })
+            | (Self::OneOf { index, .. }, RejectReason::NotOneOf { index: i, .. }) => index == i,
+            (Self::Absent(letter), RejectReason::ExcludedLetter(l))
+            | (Self::Present(letter), RejectReason::MissingIncluded(l))
+            | (Self::PresentNotAt { letter, .. }, RejectReason::MissingIncluded(l)) => letter == l,
+            (
+                Self::AtMost { letter, max },
+                RejectReason::TooManyOccurrences { letter: l, max: m },
+            ) => letter == l && max == m,
+            (
+                Self::NotAt { letter, index } | Self::PresentNotAt { letter, index },
+                RejectReason::MisplacedLetter {
+                    letter: l,
+                    index: i,
+                },
+            ) => letter == l && index == i,
+            (Self::UnusedAtWildcards, RejectReason::RepeatedKnownLetter { .. }) => true,
+            (
+                Self::Count { letter, min, max },
+                RejectReason::CountOutOfRange {
+                    letter: l,
+                    min: n,
+                    max: m,
+                    ..
+                },
+            ) => letter == l && min == n && max == m,
+            (Self::Contains(substring), RejectReason::MissingSubstring(s)) => substring == s,
+            (Self::Feedback(round), RejectReason::FeedbackMismatch(r)) => round == r,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Letter { index, letter } => write!(f, "'{}' at position {}", letter, index + 1),
+            Self::OneOf { index, letters } => write!(
+                f,
+                "One of '{}' at position {}",
+                letters.iter().collect::<String>(),
+                index + 1
+            ),
+            Self::Absent(letter) => write!(f, "No '{}'", letter),
+            Self::AtMost { letter, max } => write!(f, "'{}' at most {} times", letter, max),
+            Self::NotAt { letter, index } => write!(f, "No '{}' at position {}", letter, index + 1),
+            Self::UnusedAtWildcards => write!(f, "No known letters at unknown positions"),
+            Self::Present(letter) => write!(f, "'{}' anywhere", letter),
+            Self::PresentNotAt { letter, index } => {
+                write!(f, "'{}' anywhere but position {}", letter, index + 1)
+            }
+            Self::Count { letter, min, max } => {
+                write!(f, "'{}' from {} to {} times", letter, min, max)
+            }
+            Self::Contains(substring) => write!(f, "Contains '{}'", substring),
+            Self::Feedback(round) => write!(f, "Feedback of guess number {}", round + 1),
+        }
+    }
+}
+
+/// Every requirement of `pattern`, the constraints and `rounds` feedback rounds, in the
+/// order they are checked in.
+pub(crate) fn requirements(
+    pattern: &Word,
+    excluded: &Excluded,
+    included: &Included,
+    rounds: usize,
+) -> Vec<Requirement> {
+    let mut requirements: Vec<Requirement> = pattern
+        .0
+        .iter()
+        .enumerate()
+        .filter_map(|(index, c)| match c {
+            Character::Normal(letter) => Some(Requirement::Letter {
+                index,
+                letter: *letter,
+            }),
+            Character::OneOf(letters) => Some(Requirement::OneOf {
+                index,
+                letters: letters.clone(),
+            }),
+            Character::Wildcard => None,
+        })
+        .collect();
+
+    requirements.extend(included.letters.iter().map(|l| Requirement::Present(*l)));
+    requirements.extend(
+        included
+            .counts
+            .iter()
+            .map(|(letter, min, max)| Requirement::Count {
+                letter: *letter,
+                min: *min,
+                max: *max,
+            }),
+    );
+    requirements.extend(
+        included
+            .substrings
+            .iter()
+            .map(|s| Requirement::Contains(s.clone())),
+    );
+    requirements.extend(included.misplaced.iter().map(|(letter, position)| {
+        Requirement::PresentNotAt {
+            letter: *letter,
+            index: position.index(),
+        }
+    }));
+    requirements.extend(
+        excluded
+            .positions
+            .iter()
+            .map(|(letter, position)| Requirement::NotAt {
+                letter: *letter,
+                index: position.index(),
+            }),
+    );
+    requirements.extend(excluded.letters.iter().map(|l| Requirement::Absent(*l)));
+    if excluded.unused_at_wildcards {
+        requirements.push(Requirement::UnusedAtWildcards);
+    }
+    requirements.extend(
+        excluded
+            .max_counts
+            .iter()
+            .map(|(letter, max)| Requirement::AtMost {
+                letter: *letter,
+                max: *max,
+            }),
+    );
+    requirements.extend((0..rounds).map(Requirement::Feedback));
+    requirements
+}
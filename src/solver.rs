@@ -0,0 +1,68 @@
+use crate::{Constraints, Dictionary, Word};
+
+/// Dictionary parsed once and shared by many independent queries, e.g. behind an `Arc`
+/// in a web server. Queries never change it, so it is `Send` and `Sync`.
+#[derive(Debug, Default)]
+pub struct Solver {
+    dictionary: Dictionary,
+}
+
+impl Solver {
+    pub fn new(dictionary: Dictionary) -> Self {
+        Self { dictionary }
+    }
+
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dictionary
+    }
+
+    /// Words of the dictionary that match everything known in `constraints`.
+    pub fn candidates(&self, constraints: &Constraints) -> Vec<Word> {
+        self.dictionary
+            .filter(
+                &constraints.pattern(),
+                &constraints.excluded(),
+                &constraints.included(),
+            )
+            .into_iter()
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::{sync::Arc, thread};
+
+    use super::*;
+
+    #[test]
+    fn should_answer_queries_from_many_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Solver>();
+
+        let solver = Arc::new(Solver::new(Dictionary::new(
+            ["horse", "terse", "verse", "crane"],
+            5,
+        )));
+        let handles: Vec<_> = [("crane", "BYBBG"), ("horse", "BBGGG")]
+            .into_iter()
+            .map(|(guess, feedback)| {
+                let solver = Arc::clone(&solver);
+                thread::spawn(move || {
+                    let mut constraints = Constraints::default();
+                    constraints.add_guess(guess, feedback).unwrap();
+                    solver.candidates(&constraints)
+                })
+            })
+            .collect();
+
+        let actual: Vec<Vec<Word>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(actual[0].len(), 3);
+        assert_eq!(
+            actual[1],
+            vec![Word::new("terse").unwrap(), Word::new("verse").unwrap()]
+        );
+    }
+}